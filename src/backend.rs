@@ -0,0 +1,73 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::{BlendMode, Canvas, RenderTarget};
+
+// --- Cible de dessin ---
+//
+// Les quelques opérations dont le rendu a besoin. Un canvas SDL les fournit directement ;
+// `Framebuffer` et `Terminal` les réimplémentent sans SDL.
+
+pub trait Backend {
+    fn set_draw_color(&mut self, color: Color);
+    fn set_blend_mode(&mut self, mode: BlendMode);
+    /// Facteur appliqué aux coordonnées et aux tailles des tracés suivants
+    fn set_scale(&mut self, x: f32, y: f32) -> Result<(), String>;
+    /// Taille en pixels de la cible, sans le facteur d'échelle
+    fn output_size(&self) -> Result<(u32, u32), String>;
+    /// Remplit toute la cible avec la couleur courante, sans mélange
+    fn clear(&mut self);
+    fn draw_point(&mut self, point: (i32, i32)) -> Result<(), String>;
+    fn draw_points(&mut self, points: &[(i32, i32)]) -> Result<(), String> {
+        for &point in points {
+            self.draw_point(point)?;
+        }
+        Ok(())
+    }
+    /// Segment, extrémités comprises
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32)) -> Result<(), String>;
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String>;
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String>;
+}
+
+impl<T: RenderTarget> Backend for Canvas<T> {
+    fn set_draw_color(&mut self, color: Color) {
+        Canvas::set_draw_color(self, color);
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        Canvas::set_blend_mode(self, mode);
+    }
+
+    fn set_scale(&mut self, x: f32, y: f32) -> Result<(), String> {
+        Canvas::set_scale(self, x, y)
+    }
+
+    fn output_size(&self) -> Result<(u32, u32), String> {
+        Canvas::output_size(self)
+    }
+
+    fn clear(&mut self) {
+        Canvas::clear(self);
+    }
+
+    fn draw_point(&mut self, point: (i32, i32)) -> Result<(), String> {
+        Canvas::draw_point(self, point)
+    }
+
+    fn draw_points(&mut self, points: &[(i32, i32)]) -> Result<(), String> {
+        let points: Vec<sdl2::rect::Point> = points.iter().map(|&point| point.into()).collect();
+        Canvas::draw_points(self, points.as_slice())
+    }
+
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32)) -> Result<(), String> {
+        Canvas::draw_line(self, from, to)
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        Canvas::draw_rect(self, rect)
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        Canvas::fill_rect(self, rect)
+    }
+}
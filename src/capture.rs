@@ -0,0 +1,78 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use sdl2::pixels::PixelFormatEnum;
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, RenderTarget};
+use sdl2::surface::Surface;
+use crate::png::write_png;
+
+// --- Capture d'images ---
+
+/// Format des images écrites sur le disque
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Bmp,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Bmp => "bmp",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl std::str::FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        match text.to_ascii_lowercase().as_str() {
+            "bmp" => Ok(ImageFormat::Bmp),
+            "png" => Ok(ImageFormat::Png),
+            _ => Err(format!("unknown image format `{}` (bmp or png)", text)),
+        }
+    }
+}
+
+/// Image RGB lue depuis un canvas, 3 octets par pixel
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    pub rgb: Vec<u8>,
+}
+
+impl Frame {
+    pub fn save(&mut self, path: &str, format: ImageFormat) -> Result<(), String> {
+        match format {
+            ImageFormat::Png => write_png(path, self.width, self.height, &self.rgb),
+            ImageFormat::Bmp => {
+                let surface = Surface::from_data(&mut self.rgb, self.width, self.height, self.width * 3, PixelFormatEnum::RGB24)?;
+                surface.save_bmp(path).map_err(|e| format!("{}: {}", path, e))
+            },
+        }
+    }
+}
+
+/// Canvas dessiné par le moteur logiciel de SDL dans une surface en mémoire :
+/// ni fenêtre, ni affichage, ni carte graphique
+pub fn offscreen_canvas(width: u32, height: u32) -> Result<Canvas<Surface<'static>>, String> {
+    Surface::new(width, height, PixelFormatEnum::RGB888)?.into_canvas()
+}
+
+/// Lit l'image affichée par le canvas
+pub fn capture<T: RenderTarget>(canvas: &Canvas<T>) -> Result<Frame, String> {
+    let (width, height) = canvas.output_size()?;
+    capture_area(canvas, Rect::new(0, 0, width, height))
+}
+
+/// Lit une partie de l'image, en pixels de la cible
+pub fn capture_area<T: RenderTarget>(canvas: &Canvas<T>, area: Rect) -> Result<Frame, String> {
+    let rgb = canvas.read_pixels(area, PixelFormatEnum::RGB24)?;
+    Ok(Frame { width: area.width(), height: area.height(), rgb })
+}
+
+/// Secondes écoulées depuis l'époque Unix, pour nommer les fichiers
+pub fn timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs())
+}
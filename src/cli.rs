@@ -0,0 +1,219 @@
+use crate::capture::ImageFormat;
+use crate::config::{load_config, apply_overrides};
+use crate::game::Config;
+use crate::headless::{HeadlessOptions, DEFAULT_FRAMES, DEFAULT_OUT_DIR};
+use crate::keymap::{Keymap, load_keymap};
+use crate::video::{VideoFormat, VideoOptions, DEFAULT_FPS, DEFAULT_SECONDS, MAX_SECONDS};
+
+const MAX_SCALE: u32 = 8;
+
+pub const USAGE: &str = "\
+usage: suicideballs [options]
+
+  --config <file>      load the configuration from a `key = value` file, reloaded when it changes
+  --load <snapshot>    start from a saved world snapshot (F5 / F9 quick save and load)
+  --print-config       print the effective configuration and exit
+  --list-params        list every tunable parameter with its range and exit
+  --keymap <file>      load key bindings from an `action = key` file
+  --print-keymap       print the effective key bindings and exit
+  --seed <n>           seed the random generator, for reproducible runs
+  --headless           render without a window and write the frames as image files
+  --frames <n>         number of frames to render headless, one simulation step each (default 60)
+  --out <dir>          directory for the headless frames (default `frames`)
+  --format <bmp|png>   image format of the headless frames (default png)
+  --video <file|->     render a clip without a window, to a file or `-` for standard output
+  --video-format <f>   y4m (YUV4MPEG2, default), rgb (raw 24-bit frames) or gif (default for `.gif` files)
+  --fps <n>            frame rate of the clip (default 30)
+  --seconds <s>        length of the clip in simulated seconds, at most 3600 (default 10)
+  --scale <n>          headless and video output at n times the window size (default 1)
+  --print-frame <n>    print the first frame to the terminal, n characters wide, and exit
+  --terminal           play in the terminal (braille graphics, 24-bit colour) instead of a window
+  --<field> <value>    override any configuration field, e.g. --ball-radius 8 --max-balls 500
+  --help               show this message";
+
+/// Options de la ligne de commande
+#[derive(Default)]
+pub struct Options {
+    pub config: Config,
+    pub config_path: Option<String>,
+    pub overrides: Vec<(String, String)>, // Surcharges `--champ valeur`, réappliquées au rechargement
+    pub load_path: Option<String>,
+    pub print_config: bool,
+    pub list_params: bool,
+    pub keymap: Keymap,
+    pub print_keymap: bool,
+    pub seed: Option<u64>,
+    pub headless: Option<HeadlessOptions>,
+    pub video: Option<VideoOptions>,
+    pub print_frame: Option<u32>, // Largeur en caractères
+    pub terminal: bool,
+    pub help: bool,
+}
+
+/// Lit les arguments : le fichier `--config` est chargé en premier, puis les surcharges
+/// `--champ valeur` sont appliquées dans l'ordre.
+pub fn parse_args<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut overrides = Vec::new();
+    let mut config_path = None;
+    let mut load_path = None;
+    let mut print_config = false;
+    let mut list_params = false;
+    let mut keymap_path = None;
+    let mut print_keymap = false;
+    let mut seed = None;
+    let mut headless = false;
+    let mut frames = None;
+    let mut out_dir = None;
+    let mut format = None;
+    let mut video_path = None;
+    let mut video_format = None;
+    let mut fps = None;
+    let mut seconds = None;
+    let mut scale = None;
+    let mut print_frame = None;
+    let mut terminal = false;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let Some(flag) = arg.strip_prefix("--") else {
+            return Err(format!("unexpected argument `{}`\n\n{}", arg, USAGE));
+        };
+        // Accepter `--champ=valeur` comme `--champ valeur`
+        let (name, inline_value) = match flag.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None => (flag.to_string(), None),
+        };
+        match name.as_str() {
+            "help" => return Ok(Options { help: true, ..Options::default() }),
+            "print-config" => print_config = true,
+            "list-params" => list_params = true,
+            "print-keymap" => print_keymap = true,
+            "headless" => headless = true,
+            "terminal" => terminal = true,
+            _ => {
+                let value = match inline_value.or_else(|| args.next()) {
+                    Some(value) => value,
+                    None => return Err(format!("missing value for --{}", name)),
+                };
+                match name.as_str() {
+                    "config" => config_path = Some(value),
+                    "load" => load_path = Some(value),
+                    "keymap" => keymap_path = Some(value),
+                    "seed" => seed = Some(parse_number(&name, &value)?),
+                    "frames" => frames = Some(parse_number(&name, &value)?),
+                    "out" => out_dir = Some(value),
+                    "format" => format = Some(value.parse::<ImageFormat>()?),
+                    "video" => video_path = Some(value),
+                    "video-format" => video_format = Some(value.parse::<VideoFormat>()?),
+                    "fps" => fps = Some(parse_number(&name, &value)?),
+                    "seconds" => seconds = Some(parse_number(&name, &value)?),
+                    "scale" => scale = Some(parse_number(&name, &value)?),
+                    "print-frame" => print_frame = Some(parse_number(&name, &value)?),
+                    _ => overrides.push((name, value)),
+                }
+            },
+        }
+    }
+
+    let mut config = match &config_path {
+        Some(path) => load_config(path)?,
+        None => Config::default(),
+    };
+    apply_overrides(&mut config, &overrides)?;
+    let keymap = match &keymap_path {
+        Some(path) => load_keymap(path)?,
+        None => Keymap::default(),
+    };
+
+    let scale = scale.unwrap_or(1);
+    if !(1..=MAX_SCALE).contains(&scale) {
+        return Err(format!("--scale must be between 1 and {}", MAX_SCALE));
+    }
+    if headless && video_path.is_some() {
+        return Err("--headless and --video cannot be combined".to_string());
+    }
+    let headless = if headless {
+        Some(HeadlessOptions {
+            frames: frames.unwrap_or(DEFAULT_FRAMES),
+            out_dir: out_dir.unwrap_or_else(|| DEFAULT_OUT_DIR.to_string()),
+            format: format.unwrap_or(ImageFormat::Png),
+            scale,
+        })
+    } else if frames.is_some() || out_dir.is_some() || format.is_some() {
+        return Err("--frames, --out and --format only apply with --headless".to_string());
+    } else {
+        None
+    };
+    let video = match video_path {
+        Some(path) => {
+            let fps = fps.unwrap_or(DEFAULT_FPS);
+            let seconds = seconds.unwrap_or(DEFAULT_SECONDS);
+            if fps == 0 {
+                return Err("--fps must be positive".to_string());
+            }
+            // `inf` et `NaN` se lisent comme des nombres : sans borne, le clip ne finirait pas
+            if !(seconds > 0.0 && seconds <= MAX_SECONDS) {
+                return Err(format!("--seconds must be above 0 and at most {}", MAX_SECONDS));
+            }
+            let format = video_format.unwrap_or_else(|| VideoFormat::for_path(&path));
+            Some(VideoOptions { path, format, fps, seconds, scale })
+        },
+        None if video_format.is_some() || fps.is_some() || seconds.is_some() => {
+            return Err("--video-format, --fps and --seconds only apply with --video".to_string());
+        },
+        None => None,
+    };
+    if print_frame == Some(0) {
+        return Err("--print-frame needs at least one column".to_string());
+    }
+    if terminal && (headless.is_some() || video.is_some() || print_frame.is_some()) {
+        return Err("--terminal cannot be combined with --headless, --video or --print-frame".to_string());
+    }
+    if headless.is_none() && video.is_none() && scale != 1 {
+        return Err("--scale only applies with --headless or --video".to_string());
+    }
+
+    Ok(Options {
+        config, config_path, overrides, load_path, print_config, list_params, keymap, print_keymap, seed, headless, video, print_frame, terminal,
+        help: false,
+    })
+}
+
+fn parse_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value `{}` for --{}", value, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spawn::{OverflowPolicy, SpawnPolicyKind};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn help_is_not_an_error() {
+        assert!(parse(&["--help"]).is_ok_and(|options| options.help));
+        assert!(parse(&["--seed", "4", "--help"]).is_ok_and(|options| options.help));
+        assert!(parse(&["--seed", "4"]).is_ok_and(|options| !options.help));
+    }
+
+    #[test]
+    fn clip_length_is_bounded() {
+        assert!(parse(&["--video", "clip.y4m", "--seconds", "2.5"]).is_ok_and(|options| options.video.is_some_and(|video| video.seconds == 2.5)));
+        for seconds in ["0", "-1", "inf", "NaN", "3601"] {
+            assert!(parse(&["--video", "clip.y4m", "--seconds", seconds]).is_err(), "{}", seconds);
+        }
+    }
+
+    #[test]
+    fn policy_counts_must_be_whole_numbers() {
+        let options = parse(&["--overflow-policy", "dynamic_cap 800", "--spawn-policy", "fixed 20"]).unwrap();
+        assert_eq!(options.config.overflow_policy, OverflowPolicy::DynamicCap { ceiling: 800 });
+        assert_eq!(options.config.spawn_policy, SpawnPolicyKind::FixedPopulation { population: 20 });
+        assert!(parse(&["--overflow-policy", "dynamic_cap 3.7"]).is_err());
+        assert!(parse(&["--spawn-policy", "fixed 2.5"]).is_err());
+        assert!(parse(&["--spawn-policy", "fixed -1"]).is_err());
+    }
+}
@@ -0,0 +1,563 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant, SystemTime};
+use crate::game::{Config, Emitter, Vector2D, MAX_EMITTER_RATE};
+use crate::params::{PARAMS, MAX_BALLS_TO_SPAWN, find_param};
+use crate::spawn::{SpawnPolicyKind, OverflowPolicy};
+
+const SPAWN_MARGIN: f32 = 10.0; // Marge entre une balle qui apparaît et l'anneau
+
+// --- Validation ---
+
+/// Problème détecté dans une configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigError {
+    /// Valeur infinie ou NaN
+    NotFinite { field: &'static str },
+    /// Valeur en dehors de l'intervalle autorisé (bornes incluses)
+    OutOfRange { field: &'static str, value: f32, min: f32, max: f32 },
+    /// La simulation est plus large que la fenêtre
+    SimWiderThanScreen { sim_width: u32, screen_width: u32 },
+    /// Pas de place pour faire apparaître une balle dans l'anneau
+    BallTooLargeForCircle { ball_radius: f32, circle_radius: f32 },
+    /// L'anneau est plus épais que son rayon
+    RingTooThick { circle_thickness: f32, circle_radius: f32 },
+    /// Une cellule plus petite qu'un diamètre fait manquer des collisions
+    GridCellTooSmall { grid_cell_size: f32, ball_diameter: f32 },
+    /// La plage de vitesse d'un émetteur est inversée
+    EmitterSpeedRange { emitter: usize, speed_min: f32, speed_max: f32 },
+    /// Le plafond dynamique est inférieur à max_balls
+    DynamicCapBelowMax { ceiling: usize, max_balls: usize },
+    /// La population fixe ne tient pas sous le plafond de balles
+    PopulationAboveCap { population: usize, cap: usize },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::NotFinite { field } => write!(f, "{} must be a finite number", field),
+            ConfigError::OutOfRange { field, value, min, max } if *max == f32::MAX => {
+                write!(f, "{} = {} must be at least {}", field, value, min)
+            },
+            ConfigError::OutOfRange { field, value, min, max } => {
+                write!(f, "{} = {} is outside {}..{}", field, value, min, max)
+            },
+            ConfigError::SimWiderThanScreen { sim_width, screen_width } => {
+                write!(f, "sim_width {} > screen_width {}", sim_width, screen_width)
+            },
+            ConfigError::BallTooLargeForCircle { ball_radius, circle_radius } => {
+                write!(f, "ball_radius {} + {} >= circle_radius {}", ball_radius, SPAWN_MARGIN, circle_radius)
+            },
+            ConfigError::RingTooThick { circle_thickness, circle_radius } => {
+                write!(f, "circle_thickness {} > circle_radius {}", circle_thickness, circle_radius)
+            },
+            ConfigError::GridCellTooSmall { grid_cell_size, ball_diameter } => {
+                write!(f, "grid_cell_size {} < ball diameter {}", grid_cell_size, ball_diameter)
+            },
+            ConfigError::EmitterSpeedRange { emitter, speed_min, speed_max } => {
+                write!(f, "emitter {}: speed_min {} > speed_max {}", emitter, speed_min, speed_max)
+            },
+            ConfigError::DynamicCapBelowMax { ceiling, max_balls } => {
+                write!(f, "dynamic cap {} < max_balls {}", ceiling, max_balls)
+            },
+            ConfigError::PopulationAboveCap { population, cap } => {
+                write!(f, "fixed population {} > ball cap {}", population, cap)
+            },
+        }
+    }
+}
+
+impl Config {
+    /// Vérifie chaque champ et les contraintes entre champs.
+    /// Retourne toutes les erreurs trouvées, pas seulement la première.
+    pub fn validate(&self) -> Result<(), Vec<ConfigError>> {
+        let mut errors = Vec::new();
+
+        for param in PARAMS {
+            check_range(&mut errors, param.name, (param.get)(self), param.min, param.max);
+        }
+
+        if self.sim_width > self.screen_width {
+            errors.push(ConfigError::SimWiderThanScreen { sim_width: self.sim_width, screen_width: self.screen_width });
+        }
+        if self.ball_radius + SPAWN_MARGIN >= self.circle_radius {
+            errors.push(ConfigError::BallTooLargeForCircle { ball_radius: self.ball_radius, circle_radius: self.circle_radius });
+        }
+        if self.circle_thickness > self.circle_radius {
+            errors.push(ConfigError::RingTooThick { circle_thickness: self.circle_thickness, circle_radius: self.circle_radius });
+        }
+        if self.ball_radius > max_ball_radius(self) {
+            errors.push(ConfigError::GridCellTooSmall { grid_cell_size: self.grid_cell_size, ball_diameter: self.ball_radius * 2.0 });
+        }
+
+        for (i, emitter) in self.emitters.iter().enumerate() {
+            check_finite(&mut errors, "emitter position", emitter.position.x);
+            check_finite(&mut errors, "emitter position", emitter.position.y);
+            check_finite(&mut errors, "emitter direction", emitter.direction);
+            check_range(&mut errors, "emitter spread", emitter.spread, 0.0, std::f32::consts::PI);
+            check_range(&mut errors, "emitter speed_min", emitter.speed_min, 0.0, f32::MAX);
+            check_range(&mut errors, "emitter speed_max", emitter.speed_max, 0.0, f32::MAX);
+            check_range(&mut errors, "emitter rate", emitter.rate, 0.0, MAX_EMITTER_RATE);
+            if emitter.speed_min > emitter.speed_max {
+                errors.push(ConfigError::EmitterSpeedRange { emitter: i, speed_min: emitter.speed_min, speed_max: emitter.speed_max });
+            }
+        }
+
+        if let SpawnPolicyKind::ExponentialDecay { initial_rate, decay } = self.spawn_policy {
+            check_range(&mut errors, "initial_rate", initial_rate, 0.0, MAX_BALLS_TO_SPAWN);
+            check_range(&mut errors, "decay", decay, 0.0, 1.0);
+        }
+        if let OverflowPolicy::DynamicCap { ceiling } = self.overflow_policy
+            && ceiling < self.max_balls
+        {
+            errors.push(ConfigError::DynamicCapBelowMax { ceiling, max_balls: self.max_balls });
+        }
+        // Au-delà du plafond, la politique redemanderait les balles manquantes à chaque tick
+        if let SpawnPolicyKind::FixedPopulation { population } = self.spawn_policy {
+            let cap = match self.overflow_policy {
+                OverflowPolicy::DynamicCap { ceiling } => ceiling.max(self.max_balls),
+                _ => self.max_balls,
+            };
+            if population > cap {
+                errors.push(ConfigError::PopulationAboveCap { population, cap });
+            }
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+/// Plus grand rayon de balle : une cellule de la grille doit contenir un diamètre pour que
+/// les collisions entre cellules voisines suffisent
+pub fn max_ball_radius(config: &Config) -> f32 {
+    config.grid_cell_size * 0.5
+}
+
+fn check_finite(errors: &mut Vec<ConfigError>, field: &'static str, value: f32) {
+    if !value.is_finite() {
+        errors.push(ConfigError::NotFinite { field });
+    }
+}
+
+fn check_range(errors: &mut Vec<ConfigError>, field: &'static str, value: f32, min: f32, max: f32) {
+    if !value.is_finite() {
+        errors.push(ConfigError::NotFinite { field });
+    } else if value < min || value > max {
+        errors.push(ConfigError::OutOfRange { field, value, min, max });
+    }
+}
+
+// --- Fichier de configuration ---
+
+/// Erreur dans un fichier de configuration, avec son numéro de ligne (à partir de 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Lit une configuration au format `clé = valeur` (un champ par ligne, `#` pour les commentaires).
+/// Les champs absents gardent leur valeur par défaut ; chaque ligne `emitter` ajoute un émetteur.
+pub fn parse_config(text: &str) -> Result<Config, Vec<ParseError>> {
+    let mut config = Config::default();
+    apply_config_text(&mut config, text)?;
+    Ok(config)
+}
+
+/// Applique à `config` les champs présents dans le texte et retourne leurs noms. Les lignes
+/// `emitter` remplacent les émetteurs en place ; sans elles, ils sont conservés.
+fn apply_config_text(config: &mut Config, text: &str) -> Result<Vec<String>, Vec<ParseError>> {
+    let mut present: Vec<String> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (index, raw_line) in text.lines().enumerate() {
+        let line = raw_line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        let result = match line.split_once('=') {
+            Some((key, value)) => {
+                let key = key.trim();
+                if key == "emitter" && !present.iter().any(|name| name == key) {
+                    config.emitters.clear();
+                }
+                present.push(key.to_string());
+                set_field(config, key, unquote(value.trim()))
+            },
+            None => Err(format!("expected `key = value`, found `{}`", line)),
+        };
+        if let Err(message) = result {
+            errors.push(ParseError { line: index + 1, message });
+        }
+    }
+
+    if errors.is_empty() { Ok(present) } else { Err(errors) }
+}
+
+/// Charge un fichier de configuration ; les erreurs sont préfixées par le chemin du fichier
+pub fn load_config(path: &str) -> Result<Config, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    parse_config(&text).map_err(|errors| format_errors(path, &errors))
+}
+
+fn format_errors(path: &str, errors: &[ParseError]) -> String {
+    errors.iter().map(|error| format!("{}:{}: {}", path, error.line, error.message)).collect::<Vec<_>>().join("\n")
+}
+
+/// Applique des surcharges `(champ, valeur)` venues de la ligne de commande
+pub fn apply_overrides(config: &mut Config, overrides: &[(String, String)]) -> Result<(), String> {
+    for (name, value) in overrides {
+        set_field(config, &name.replace('-', "_"), value).map_err(|e| format!("--{}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+/// Modifie un champ à partir de son nom et de sa valeur textuelle
+pub fn set_field(config: &mut Config, key: &str, value: &str) -> Result<(), String> {
+    if let Some(param) = find_param(key) {
+        (param.set)(config, param.parse(value)?);
+        return Ok(());
+    }
+    // Champs structurés, hors du registre
+    match key {
+        "spawn_policy" => config.spawn_policy = value.parse()?,
+        "overflow_policy" => config.overflow_policy = value.parse()?,
+        "emitter" => config.emitters.push(parse_emitter(value)?),
+        _ => return Err(format!("unknown key `{}`", key)),
+    }
+    Ok(())
+}
+
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value `{}` for `{}`", value, key))
+}
+
+fn unquote(value: &str) -> &str {
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value)
+}
+
+/// Découpe une valeur en un nom facultatif suivi de nombres
+fn parse_words(key: &str, value: &str) -> Result<(String, Vec<f32>), String> {
+    let mut words = value.split_whitespace().peekable();
+    let name = match words.peek() {
+        Some(first) if first.parse::<f32>().is_err() => first.to_lowercase(),
+        _ => String::new(),
+    };
+    if !name.is_empty() {
+        words.next();
+    }
+    let numbers = words.map(|word| parse_value(key, word)).collect::<Result<Vec<f32>, _>>()?;
+    Ok((name, numbers))
+}
+
+/// Nombre entier positif, exact en `f32` (sous 2^24) : `3.7` n'est pas un nombre de balles
+fn is_count(value: f32) -> bool {
+    (0.0..16_777_216.0).contains(&value) && value.fract() == 0.0
+}
+
+/// `fountain X Y`, `hose X Y TARGET_X TARGET_Y`, ou
+/// `X Y DIRECTION SPREAD SPEED_MIN SPEED_MAX RATE`
+fn parse_emitter(value: &str) -> Result<Emitter, String> {
+    let (name, numbers) = parse_words("emitter", value)?;
+    match (name.as_str(), numbers.as_slice()) {
+        ("fountain", &[x, y]) => Ok(Emitter::fountain(Vector2D { x, y })),
+        ("hose", &[x, y, tx, ty]) => Ok(Emitter::hose(Vector2D { x, y }, Vector2D { x: tx, y: ty })),
+        ("", &[x, y, direction, spread, speed_min, speed_max, rate]) => {
+            Ok(Emitter::new(Vector2D { x, y }, direction, spread, speed_min, speed_max, rate))
+        },
+        _ => Err(format!(
+            "invalid emitter `{}`: expected `fountain X Y`, `hose X Y TX TY` or 7 numbers",
+            value
+        )),
+    }
+}
+
+impl FromStr for SpawnPolicyKind {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        let (name, numbers) = parse_words("spawn_policy", value)?;
+        match (name.as_str(), numbers.as_slice()) {
+            ("multiplier", []) => Ok(SpawnPolicyKind::Multiplier),
+            ("fixed", &[population]) if is_count(population) => {
+                Ok(SpawnPolicyKind::FixedPopulation { population: population as usize })
+            },
+            ("exponential", &[initial_rate, decay]) => Ok(SpawnPolicyKind::ExponentialDecay { initial_rate, decay }),
+            ("escape_point", []) => Ok(SpawnPolicyKind::EscapePoint),
+            ("inherit_velocity", []) => Ok(SpawnPolicyKind::InheritVelocity),
+            _ => Err(format!(
+                "invalid spawn_policy `{}`: expected multiplier, fixed N, exponential RATE DECAY, escape_point or inherit_velocity",
+                value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for SpawnPolicyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SpawnPolicyKind::Multiplier => write!(f, "multiplier"),
+            SpawnPolicyKind::FixedPopulation { population } => write!(f, "fixed {}", population),
+            SpawnPolicyKind::ExponentialDecay { initial_rate, decay } => write!(f, "exponential {} {}", initial_rate, decay),
+            SpawnPolicyKind::EscapePoint => write!(f, "escape_point"),
+            SpawnPolicyKind::InheritVelocity => write!(f, "inherit_velocity"),
+        }
+    }
+}
+
+impl FromStr for OverflowPolicy {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, String> {
+        let (name, numbers) = parse_words("overflow_policy", value)?;
+        match (name.as_str(), numbers.as_slice()) {
+            ("drop", []) => Ok(OverflowPolicy::Drop),
+            ("queue", []) => Ok(OverflowPolicy::Queue),
+            ("evict_oldest", []) => Ok(OverflowPolicy::EvictOldest),
+            ("merge_smallest", []) => Ok(OverflowPolicy::MergeSmallest),
+            ("dynamic_cap", &[ceiling]) if is_count(ceiling) => {
+                Ok(OverflowPolicy::DynamicCap { ceiling: ceiling as usize })
+            },
+            _ => Err(format!(
+                "invalid overflow_policy `{}`: expected drop, queue, evict_oldest, merge_smallest or dynamic_cap N",
+                value
+            )),
+        }
+    }
+}
+
+impl fmt::Display for OverflowPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            OverflowPolicy::Drop => write!(f, "drop"),
+            OverflowPolicy::Queue => write!(f, "queue"),
+            OverflowPolicy::EvictOldest => write!(f, "evict_oldest"),
+            OverflowPolicy::MergeSmallest => write!(f, "merge_smallest"),
+            OverflowPolicy::DynamicCap { ceiling } => write!(f, "dynamic_cap {}", ceiling),
+        }
+    }
+}
+
+/// Écrit la configuration au format de fichier, relisible par `parse_config`
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for param in PARAMS {
+            writeln!(f, "{} = {}", param.name, param.format((param.get)(self)))?;
+        }
+        writeln!(f, "spawn_policy = {}", self.spawn_policy)?;
+        writeln!(f, "overflow_policy = {}", self.overflow_policy)?;
+        for emitter in &self.emitters {
+            writeln!(
+                f,
+                "emitter = {} {} {} {} {} {} {}",
+                emitter.position.x, emitter.position.y, emitter.direction, emitter.spread,
+                emitter.speed_min, emitter.speed_max, emitter.rate
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// --- Rechargement à chaud ---
+
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Surveille la date de modification du fichier de configuration pour le relire à chaud.
+/// Seuls les champs présents dans le fichier sont relus : les réglages changés en cours de route
+/// (clavier, panneau, console) gardent leur valeur pour les autres. Les surcharges de la ligne
+/// de commande l'emportent toujours sur le fichier.
+pub struct ConfigWatcher {
+    path: String,
+    overrides: Vec<(String, String)>,
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+impl ConfigWatcher {
+    pub fn new(path: String, overrides: Vec<(String, String)>) -> Self {
+        let modified = modification_time(&path);
+        Self { path, overrides, modified, last_check: Instant::now() }
+    }
+
+    /// Relit le fichier s'il a changé depuis la dernière lecture (vérifié deux fois par seconde),
+    /// par-dessus la configuration en cours. Retourne `None` tant que rien n'a changé.
+    pub fn poll(&mut self, current: &Config) -> Option<Result<Config, String>> {
+        if self.last_check.elapsed() < WATCH_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+
+        // Un fichier momentanément absent (sauvegarde par renommage) n'est pas une erreur
+        let modified = modification_time(&self.path)?;
+        if self.modified == Some(modified) {
+            return None;
+        }
+        self.modified = Some(modified);
+        Some(self.reload(current))
+    }
+
+    fn reload(&self, current: &Config) -> Result<Config, String> {
+        let text = std::fs::read_to_string(&self.path).map_err(|e| format!("{}: {}", self.path, e))?;
+        let mut config = current.clone();
+        let present = apply_config_text(&mut config, &text).map_err(|errors| format_errors(&self.path, &errors))?;
+        let overrides: Vec<(String, String)> = self
+            .overrides
+            .iter()
+            .filter(|(name, _)| present.contains(&name.replace('-', "_")))
+            .cloned()
+            .collect();
+        apply_overrides(&mut config, &overrides)?;
+        Ok(config)
+    }
+}
+
+fn modification_time(path: &str) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GravityMode;
+
+    /// Fichier temporaire surveillé ; `name` distingue les tests lancés en parallèle
+    fn watch(name: &str, text: &str, overrides: &[(&str, &str)]) -> (ConfigWatcher, String) {
+        let path = std::env::temp_dir().join(format!("suicideballs-{}-{}.conf", std::process::id(), name));
+        let path = path.to_string_lossy().to_string();
+        std::fs::write(&path, text).unwrap();
+        let overrides = overrides.iter().map(|&(name, value)| (name.to_string(), value.to_string())).collect();
+        (ConfigWatcher::new(path.clone(), overrides), path)
+    }
+
+    #[test]
+    fn parse_errors_carry_their_line_number() {
+        let text = "# réglages\nbounciness = 0.9\nwobble = 3\n\nfriction = lots\nball_radius 12\nmax_balls = 40\n";
+        let Err(errors) = parse_config(text) else { panic!("the text has errors") };
+        let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [3, 5, 6]);
+        assert!(errors[0].message.contains("wobble"), "{}", errors[0]);
+        assert!(errors[1].message.contains("lots"), "{}", errors[1]);
+        assert!(errors[2].message.contains("ball_radius 12"), "{}", errors[2]);
+    }
+
+    #[test]
+    fn printed_config_parses_back_unchanged() {
+        let config = Config {
+            bounciness: 0.1 + 0.2,
+            circle_rotation_speed: -0.00665,
+            gravity_mode: GravityMode::Centripetal,
+            max_balls: 120,
+            spawn_policy: SpawnPolicyKind::ExponentialDecay { initial_rate: 3.5, decay: 0.75 },
+            overflow_policy: OverflowPolicy::DynamicCap { ceiling: 300 },
+            emitters: vec![
+                Emitter::fountain(Vector2D { x: 300.0, y: 400.0 }),
+                Emitter::hose(Vector2D { x: 120.5, y: 200.25 }, Vector2D { x: 300.0, y: 150.0 }),
+            ],
+            ..Config::default()
+        };
+        assert!(parse_config(&config.to_string()).unwrap() == config);
+        assert!(parse_config(&Config::default().to_string()).unwrap() == Config::default());
+    }
+
+    #[test]
+    fn validate_rejects_inverted_ranges() {
+        let origin = Vector2D { x: 0.0, y: 0.0 };
+        let emitters = vec![Emitter::new(origin, 0.0, 0.1, 2.0, 5.0, 1.0), Emitter::new(origin, 0.0, 0.1, 5.0, 2.0, 1.0)];
+        let config = Config { emitters, ..Config::default() };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![ConfigError::EmitterSpeedRange { emitter: 1, speed_min: 5.0, speed_max: 2.0 }]);
+
+        let config = Config { sim_width: 900, screen_width: 800, ..Config::default() };
+        assert_eq!(config.validate().unwrap_err(), vec![ConfigError::SimWiderThanScreen { sim_width: 900, screen_width: 800 }]);
+    }
+
+    #[test]
+    fn validate_rejects_unbounded_rates() {
+        for rate in [1001.0, 1e30, f32::MAX] {
+            let emitter = Emitter::new(Vector2D { x: 0.0, y: 0.0 }, 0.0, 0.1, 1.0, 2.0, rate);
+            let config = Config { emitters: vec![emitter], ..Config::default() };
+            assert!(matches!(config.validate().unwrap_err()[..], [ConfigError::OutOfRange { field: "emitter rate", .. }]), "{}", rate);
+        }
+        for initial_rate in [101.0, 1e30, f32::MAX] {
+            let config = Config { spawn_policy: SpawnPolicyKind::ExponentialDecay { initial_rate, decay: 0.8 }, ..Config::default() };
+            assert!(matches!(config.validate().unwrap_err()[..], [ConfigError::OutOfRange { field: "initial_rate", .. }]), "{}", initial_rate);
+        }
+        let emitter = Emitter::new(Vector2D { x: 0.0, y: 0.0 }, 0.0, 0.1, 1.0, 2.0, 1000.0);
+        let exponential = SpawnPolicyKind::ExponentialDecay { initial_rate: 100.0, decay: 0.8 };
+        assert!(Config { emitters: vec![emitter], spawn_policy: exponential, ..Config::default() }.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_balls_wider_than_a_grid_cell() {
+        let config = Config { grid_cell_size: 10.0, ball_radius: 6.0, ..Config::default() };
+        assert_eq!(config.validate().unwrap_err(), vec![ConfigError::GridCellTooSmall { grid_cell_size: 10.0, ball_diameter: 12.0 }]);
+        let config = Config { grid_cell_size: 10.0, ball_radius: 5.0, ..Config::default() };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_a_ring_thicker_than_its_radius() {
+        let config = Config { circle_radius: 50.0, circle_thickness: 60.0, ..Config::default() };
+        assert_eq!(config.validate().unwrap_err(), vec![ConfigError::RingTooThick { circle_thickness: 60.0, circle_radius: 50.0 }]);
+        let mut config = Config::default();
+        assert!(set_field(&mut config, "circle_thickness", "1e9").is_ok());
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn validate_rejects_zero_sizes() {
+        for field in ["screen_width", "sim_width", "circle_radius", "grid_cell_size"] {
+            let mut config = Config::default();
+            apply_overrides(&mut config, &[(field.to_string(), "0".to_string())]).unwrap();
+            let errors = config.validate().unwrap_err();
+            assert!(errors.iter().any(|error| matches!(error, ConfigError::OutOfRange { field: f, value: 0.0, .. } if *f == field)), "{}: {:?}", field, errors);
+        }
+    }
+
+    #[test]
+    fn validate_rejects_a_population_above_the_cap() {
+        let fixed = SpawnPolicyKind::FixedPopulation { population: 80 };
+        let config = Config { max_balls: 50, spawn_policy: fixed, ..Config::default() };
+        assert_eq!(config.validate().unwrap_err(), vec![ConfigError::PopulationAboveCap { population: 80, cap: 50 }]);
+        let config = Config { overflow_policy: OverflowPolicy::DynamicCap { ceiling: 100 }, ..config };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn reload_keeps_values_absent_from_the_file() {
+        let (watcher, path) = watch("absent", "# réglage à chaud\ncircle_gap_angle = 0.5\n", &[]);
+        let current = Config { bounciness: 0.5, balls_to_spawn: 7, ..Config::default() };
+        let reloaded = watcher.reload(&current).unwrap();
+        assert_eq!(reloaded.circle_gap_angle, 0.5);
+        assert_eq!((reloaded.bounciness, reloaded.balls_to_spawn), (0.5, 7));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn reload_replaces_emitters_only_when_listed() {
+        let current = Config { emitters: vec![Emitter::new(Vector2D { x: 1.0, y: 2.0 }, 0.0, 0.1, 1.0, 2.0, 3.0)], ..Config::default() };
+        let (watcher, path) = watch("kept", "friction = 0.95\n", &[]);
+        assert!(watcher.reload(&current).unwrap().emitters == current.emitters);
+        std::fs::remove_file(path).unwrap();
+
+        let (watcher, path) = watch("listed", "emitter = 5 6 0 0.1 1 2 3\nemitter = 7 8 0 0.1 1 2 3\n", &[]);
+        let positions: Vec<Vector2D> = watcher.reload(&current).unwrap().emitters.iter().map(|e| e.position).collect();
+        assert_eq!(positions, [Vector2D { x: 5.0, y: 6.0 }, Vector2D { x: 7.0, y: 8.0 }]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn overrides_win_over_the_reloaded_file() {
+        let (watcher, path) = watch("overrides", "bounciness = 0.4\nfriction = 0.95\n", &[("bounciness", "0.9"), ("balls-to-spawn", "3")]);
+        let current = Config { balls_to_spawn: 9, ..Config::default() };
+        let reloaded = watcher.reload(&current).unwrap();
+        assert_eq!((reloaded.bounciness, reloaded.friction, reloaded.balls_to_spawn), (0.9, 0.95, 9));
+        std::fs::remove_file(path).unwrap();
+    }
+}
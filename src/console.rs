@@ -0,0 +1,232 @@
+use std::collections::VecDeque;
+use crate::config::set_field;
+use crate::game::{World, Vector2D, apply_config, spawn_balls, clear_balls};
+use crate::params::{find_param, set_param_text};
+use crate::rng::SimRng;
+use crate::snapshot::{save_to_file, load_into};
+use crate::svg::write_svg;
+
+// --- Console de développement ---
+
+const OUTPUT_LINES: usize = 100;   // Lignes de sortie conservées
+const HISTORY_LENGTH: usize = 50;  // Commandes conservées
+const MAX_SPAWN: usize = 10_000;   // Balles demandées en une commande
+
+const HELP: &str = "\
+set NAME VALUE      change a parameter, e.g. set bounciness 0.9
+spawn N [at X Y]    add N balls, at a point or at random in the ring
+clear               remove every ball
+seed N              reseed the random generator
+save NAME           write a snapshot to NAME.snap
+load NAME           restore a snapshot from NAME.snap
+svg NAME            write the current frame to NAME.svg
+gravity MODE        centripetal or vertical";
+
+/// Console déroulante (touche sous Échap) : ligne en cours, sortie et historique
+pub struct Console {
+    pub open: bool,
+    pub input: String,
+    pub output: VecDeque<String>,
+    history: Vec<String>,
+    history_index: Option<usize>, // Position dans l'historique pendant la navigation
+}
+
+impl Console {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            input: String::new(),
+            output: VecDeque::new(),
+            history: Vec::new(),
+            history_index: None,
+        }
+    }
+
+    fn print(&mut self, text: &str) {
+        for line in text.lines() {
+            if self.output.len() == OUTPUT_LINES {
+                self.output.pop_front();
+            }
+            self.output.push_back(line.to_string());
+        }
+    }
+}
+
+pub fn toggle_console(world: &mut World) {
+    world.console.open = !world.console.open;
+    // La touche relâchée pendant que la console a le clavier ne serait jamais vue
+    world.history.rewinding = false;
+}
+
+pub fn console_type(world: &mut World, text: &str) {
+    world.console.input.push_str(text);
+}
+
+pub fn console_backspace(world: &mut World) {
+    world.console.input.pop();
+}
+
+/// Parcourt l'historique : `-1` pour la commande précédente, `1` pour la suivante
+pub fn console_history(world: &mut World, delta: i32) {
+    let console = &mut world.console;
+    if console.history.is_empty() {
+        return;
+    }
+    let last = console.history.len() - 1;
+    console.history_index = match (console.history_index, delta < 0) {
+        (None, true) => Some(last),
+        (None, false) => None,
+        (Some(index), true) => Some(index.saturating_sub(1)),
+        (Some(index), false) if index < last => Some(index + 1),
+        (Some(_), false) => None,
+    };
+    console.input = match console.history_index {
+        Some(index) => console.history[index].clone(),
+        None => String::new(),
+    };
+}
+
+/// Exécute la ligne saisie et affiche son résultat
+pub fn console_submit(world: &mut World) {
+    let line = std::mem::take(&mut world.console.input).trim().to_string();
+    world.console.history_index = None;
+    if line.is_empty() {
+        return;
+    }
+    world.console.print(&format!("> {}", line));
+    if world.console.history.last() != Some(&line) {
+        if world.console.history.len() == HISTORY_LENGTH {
+            world.console.history.remove(0);
+        }
+        world.console.history.push(line.clone());
+    }
+
+    let message = match run_command(world, &line) {
+        Ok(message) => message,
+        Err(message) => format!("error: {}", message),
+    };
+    world.console.print(&message);
+}
+
+fn run_command(world: &mut World, line: &str) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["help"] => Ok(HELP.to_string()),
+        ["set", key, value @ ..] if !value.is_empty() => set(world, key, &value.join(" ")),
+        ["spawn", count] => spawn(world, count, None),
+        ["spawn", count, "at", x, y] => {
+            let position = Vector2D { x: parse_number(x)?, y: parse_number(y)? };
+            spawn(world, count, Some(position))
+        },
+        ["clear"] => {
+            clear_balls(world);
+            Ok("cleared".to_string())
+        },
+        ["seed", seed] => {
+            let seed = seed.parse().map_err(|_| format!("invalid seed `{}`", seed))?;
+            world.rng = SimRng::seed_from_u64(seed);
+            // Les sauvegardes et les captures d'écran indiquent la graine en cours
+            world.seed = world.rng.state;
+            Ok(format!("seed {}", seed))
+        },
+        ["save", name] => {
+            let path = snapshot_path(name);
+            save_to_file(world, &path)?;
+            Ok(format!("saved {}", path))
+        },
+        ["load", name] => {
+            let path = snapshot_path(name);
+            load_into(world, &path)?;
+            Ok(format!("loaded {}", path))
+        },
+        ["svg", name] => {
+            let path = if name.ends_with(".svg") { name.to_string() } else { format!("{}.svg", name) };
+            write_svg(world, &path)?;
+            Ok(format!("saved {}", path))
+        },
+        ["gravity", mode] => {
+            set_param_text(world, "gravity_mode", mode)?;
+            Ok(format!("gravity {}", mode))
+        },
+        [command, ..] => Err(format!("unknown command or arguments `{}`, try `help`", command)),
+        [] => Ok(String::new()),
+    }
+}
+
+/// `set` passe par le registre ; les champs structurés (politiques, émetteurs) par le format du fichier
+fn set(world: &mut World, key: &str, value: &str) -> Result<String, String> {
+    if find_param(key).is_some() {
+        set_param_text(world, key, value)?;
+    } else {
+        let mut config = world.config.clone();
+        set_field(&mut config, key, value)?;
+        // Un émetteur ajouté rejoint aussi ceux de la simulation en cours
+        let new_emitter = config.emitters.get(world.config.emitters.len()).cloned();
+        if !apply_config(world, config) {
+            return Err(format!("{} = {} gives an invalid configuration", key, value));
+        }
+        world.emitters.extend(new_emitter);
+    }
+    Ok(format!("{} = {}", key, value))
+}
+
+fn spawn(world: &mut World, count: &str, position: Option<Vector2D>) -> Result<String, String> {
+    let count: usize = count.parse().map_err(|_| format!("invalid count `{}`", count))?;
+    if count > MAX_SPAWN {
+        return Err(format!("at most {} balls at once", MAX_SPAWN));
+    }
+    let spawned = spawn_balls(world, count, position);
+    Ok(format!("spawned {} of {}", spawned, count))
+}
+
+fn parse_number(text: &str) -> Result<f32, String> {
+    text.parse().map_err(|_| format!("invalid number `{}`", text))
+}
+
+fn snapshot_path(name: &str) -> String {
+    if name.ends_with(".snap") { name.to_string() } else { format!("{}.snap", name) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+    use crate::spawn::{OverflowPolicy, SpawnRequest};
+
+    fn world(config: Config) -> World {
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        world.balls.clear();
+        world
+    }
+
+    #[test]
+    fn spawn_counts_only_its_own_balls() {
+        let mut world = world(Config { max_balls: 5, overflow_policy: OverflowPolicy::Queue, ..Config::default() });
+        world.ball_cap = 5;
+        world.pending_spawns.extend([SpawnRequest { position: None, velocity: None }; 3]);
+        assert_eq!(run_command(&mut world, "spawn 4"), Ok("spawned 2 of 4".to_string()));
+        assert_eq!(world.balls.len(), 5);
+        assert_eq!(run_command(&mut world, "spawn 1"), Ok("spawned 0 of 1".to_string()));
+    }
+
+    #[test]
+    fn seed_is_recorded_in_snapshots() {
+        let mut world = world(Config::default());
+        assert_eq!(run_command(&mut world, "seed 42"), Ok("seed 42".to_string()));
+        assert_eq!((world.seed, world.rng), (42, SimRng::seed_from_u64(42)));
+        let snapshot = crate::snapshot::Snapshot::capture(&world);
+        assert_eq!(snapshot.seed, 42);
+        assert!(snapshot.to_string().contains("seed = 42"));
+    }
+
+    #[test]
+    fn set_refuses_out_of_range_values() {
+        let mut world = world(Config::default());
+        let bounciness = world.config.bounciness;
+        assert_eq!(run_command(&mut world, "set bounciness 5"), Err("bounciness = 5 is outside 0.1..1.2".to_string()));
+        assert_eq!(world.config.bounciness, bounciness);
+        assert_eq!(run_command(&mut world, "set bounciness 0.5"), Ok("bounciness = 0.5".to_string()));
+        assert_eq!(world.config.bounciness, 0.5);
+    }
+}
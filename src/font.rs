@@ -0,0 +1,308 @@
+// --- Police bitmap 5 x 7 ---
+//
+// ASCII imprimable (32 à 126) et lettres Latin-1. Les minuscules tiennent sur les cinq lignes
+// du bas, ce qui laisse les deux lignes du haut aux accents ; les capitales accentuées sont
+// réduites à cinq lignes pour la même raison.
+
+pub const GLYPH_WIDTH: u32 = 5;
+pub const GLYPH_HEIGHT: u32 = 7;
+const ADVANCE: u32 = GLYPH_WIDTH + 1;      // Une colonne entre deux caractères
+const LINE_HEIGHT: u32 = GLYPH_HEIGHT + 2; // Deux lignes entre deux lignes de texte
+
+/// Une ligne de pixels par octet, le bit 4 pour la colonne de gauche
+pub type Glyph = [u8; 7];
+
+const BLANK: Glyph = [0; 7];
+
+/// Alignement de chaque ligne d'un texte par rapport à son abscisse
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+/// Largeur et hauteur en pixels d'un texte de plusieurs lignes, sans l'espacement
+/// après le dernier caractère ni après la dernière ligne
+pub fn text_size(text: &str, scale: u32) -> (u32, u32) {
+    let width = text.split('\n').map(|line| line_width(line, scale)).max().unwrap_or(0);
+    let lines = text.split('\n').count() as u32;
+    (width, ((lines - 1) * LINE_HEIGHT + GLYPH_HEIGHT) * scale)
+}
+
+fn line_width(line: &str, scale: u32) -> u32 {
+    (line.chars().count() as u32 * ADVANCE).saturating_sub(ADVANCE - GLYPH_WIDTH) * scale
+}
+
+/// Coin de chaque pixel allumé d'un texte, pixels de `scale` x `scale`.
+/// Les lignes sont séparées par `\n` et alignées chacune sur `x` ; `y` est le haut de la première.
+pub fn text_pixels(text: &str, x: i32, y: i32, scale: u32, align: Align) -> Vec<(i32, i32)> {
+    let mut pixels = Vec::new();
+    for (line_index, line) in text.split('\n').enumerate() {
+        let width = line_width(line, scale) as i32;
+        let mut current_x = match align {
+            Align::Left => x,
+            Align::Center => x - width / 2,
+            Align::Right => x - width,
+        };
+        let top = y + (line_index as u32 * LINE_HEIGHT * scale) as i32;
+        for c in line.chars() {
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for column in 0..GLYPH_WIDTH {
+                    if bits & (1 << (GLYPH_WIDTH - 1 - column)) != 0 {
+                        pixels.push((current_x + (column * scale) as i32, top + (row as u32 * scale) as i32));
+                    }
+                }
+            }
+            current_x += (ADVANCE * scale) as i32;
+        }
+    }
+    pixels
+}
+
+/// Dessin d'un caractère ; vide pour ceux que la police ne connaît pas
+pub fn glyph(c: char) -> Glyph {
+    match c {
+        ' '..='~' => ASCII[c as usize - ' ' as usize],
+        '\u{a0}' => BLANK, // Espace insécable
+        'À'..='ÿ' => latin1_glyph(c),
+        _ => BLANK,
+    }
+}
+
+// --- Lettres Latin-1 ---
+
+#[derive(Clone, Copy)]
+enum Accent {
+    Grave,
+    Acute,
+    Circumflex,
+    Tilde,
+    Diaeresis,
+    Ring,
+}
+
+impl Accent {
+    /// Les deux lignes du haut
+    fn rows(self) -> [u8; 2] {
+        match self {
+            Accent::Grave => [0b01000, 0b00100],
+            Accent::Acute => [0b00010, 0b00100],
+            Accent::Circumflex => [0b00100, 0b01010],
+            Accent::Tilde => [0b01101, 0b10110],
+            Accent::Diaeresis => [0b01010, 0b00000],
+            Accent::Ring => [0b00100, 0b01010],
+        }
+    }
+}
+
+/// Lettre accentuée : la lettre de base et son accent
+const ACCENTED: [(char, char, Accent); 51] = [
+    ('À', 'A', Accent::Grave), ('Á', 'A', Accent::Acute), ('Â', 'A', Accent::Circumflex),
+    ('Ã', 'A', Accent::Tilde), ('Ä', 'A', Accent::Diaeresis), ('Å', 'A', Accent::Ring),
+    ('È', 'E', Accent::Grave), ('É', 'E', Accent::Acute), ('Ê', 'E', Accent::Circumflex), ('Ë', 'E', Accent::Diaeresis),
+    ('Ì', 'I', Accent::Grave), ('Í', 'I', Accent::Acute), ('Î', 'I', Accent::Circumflex), ('Ï', 'I', Accent::Diaeresis),
+    ('Ñ', 'N', Accent::Tilde),
+    ('Ò', 'O', Accent::Grave), ('Ó', 'O', Accent::Acute), ('Ô', 'O', Accent::Circumflex),
+    ('Õ', 'O', Accent::Tilde), ('Ö', 'O', Accent::Diaeresis),
+    ('Ù', 'U', Accent::Grave), ('Ú', 'U', Accent::Acute), ('Û', 'U', Accent::Circumflex), ('Ü', 'U', Accent::Diaeresis),
+    ('Ý', 'Y', Accent::Acute),
+    ('à', 'a', Accent::Grave), ('á', 'a', Accent::Acute), ('â', 'a', Accent::Circumflex),
+    ('ã', 'a', Accent::Tilde), ('ä', 'a', Accent::Diaeresis), ('å', 'a', Accent::Ring),
+    ('è', 'e', Accent::Grave), ('é', 'e', Accent::Acute), ('ê', 'e', Accent::Circumflex), ('ë', 'e', Accent::Diaeresis),
+    ('ì', 'i', Accent::Grave), ('í', 'i', Accent::Acute), ('î', 'i', Accent::Circumflex), ('ï', 'i', Accent::Diaeresis),
+    ('ñ', 'n', Accent::Tilde),
+    ('ò', 'o', Accent::Grave), ('ó', 'o', Accent::Acute), ('ô', 'o', Accent::Circumflex),
+    ('õ', 'o', Accent::Tilde), ('ö', 'o', Accent::Diaeresis),
+    ('ù', 'u', Accent::Grave), ('ú', 'u', Accent::Acute), ('û', 'u', Accent::Circumflex), ('ü', 'u', Accent::Diaeresis),
+    ('ý', 'y', Accent::Acute), ('ÿ', 'y', Accent::Diaeresis),
+];
+
+fn latin1_glyph(c: char) -> Glyph {
+    match c {
+        'Æ' => [0b01111, 0b10100, 0b10100, 0b11110, 0b10100, 0b10100, 0b10111],
+        'Ç' => [0b01110, 0b10001, 0b10000, 0b10001, 0b01110, 0b00100, 0b01100],
+        'Ð' => [0b11100, 0b10010, 0b10001, 0b11101, 0b10001, 0b10010, 0b11100],
+        '×' => [0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b00000],
+        'Ø' => [0b01110, 0b10011, 0b10101, 0b10101, 0b10101, 0b11001, 0b01110],
+        'Þ' => [0b10000, 0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000],
+        'ß' => [0b01100, 0b10010, 0b10010, 0b10110, 0b10001, 0b10001, 0b10110],
+        'æ' => [0b00000, 0b00000, 0b11010, 0b00101, 0b01111, 0b10100, 0b01111],
+        'ç' => [0b00000, 0b01110, 0b10000, 0b10001, 0b01110, 0b00100, 0b01100],
+        'ð' => [0b10100, 0b01000, 0b10100, 0b00010, 0b01111, 0b10001, 0b01110],
+        '÷' => [0b00000, 0b00100, 0b00000, 0b11111, 0b00000, 0b00100, 0b00000],
+        'ø' => [0b00000, 0b00000, 0b01111, 0b10011, 0b10101, 0b11001, 0b11110],
+        'þ' => [0b10000, 0b10000, 0b11110, 0b10001, 0b10001, 0b11110, 0b10000],
+        _ => match ACCENTED.iter().find(|&&(accented, _, _)| accented == c) {
+            Some(&(_, base, accent)) => {
+                let mut accented = BLANK;
+                accented[..2].copy_from_slice(&accent.rows());
+                accented[2..].copy_from_slice(&body(base));
+                accented
+            },
+            None => BLANK,
+        },
+    }
+}
+
+/// Lettre de base sur les cinq lignes du bas : telle quelle pour une minuscule
+/// (le point du `i` laisse la place à l'accent), réduite pour une capitale
+fn body(base: char) -> [u8; 5] {
+    match base {
+        'A' => [0b01110, 0b10001, 0b11111, 0b10001, 0b10001],
+        'E' => [0b11111, 0b10000, 0b11110, 0b10000, 0b11111],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b01110],
+        'N' => [0b10001, 0b11001, 0b10101, 0b10011, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b01110],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'Y' => [0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        _ => {
+            let mut rows = [0; 5];
+            rows.copy_from_slice(&glyph(base)[2..]);
+            rows
+        },
+    }
+}
+
+const ASCII: [Glyph; 95] = [
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000], // espace
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00000, 0b00100], // !
+    [0b01010, 0b01010, 0b01010, 0b00000, 0b00000, 0b00000, 0b00000], // "
+    [0b01010, 0b01010, 0b11111, 0b01010, 0b11111, 0b01010, 0b01010], // #
+    [0b00100, 0b01111, 0b10100, 0b01110, 0b00101, 0b11110, 0b00100], // $
+    [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011], // %
+    [0b01100, 0b10010, 0b10100, 0b01000, 0b10101, 0b10010, 0b01101], // &
+    [0b01100, 0b00100, 0b01000, 0b00000, 0b00000, 0b00000, 0b00000], // '
+    [0b00010, 0b00100, 0b01000, 0b01000, 0b01000, 0b00100, 0b00010], // (
+    [0b01000, 0b00100, 0b00010, 0b00010, 0b00010, 0b00100, 0b01000], // )
+    [0b00000, 0b00100, 0b10101, 0b01110, 0b10101, 0b00100, 0b00000], // *
+    [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000], // +
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b00100, 0b01000], // ,
+    [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000], // -
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100], // .
+    [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000], // /
+    [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110], // 0
+    [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // 1
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111], // 2
+    [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110], // 3
+    [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010], // 4
+    [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110], // 5
+    [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110], // 6
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000], // 7
+    [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110], // 8
+    [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100], // 9
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b01100, 0b00000], // :
+    [0b00000, 0b01100, 0b01100, 0b00000, 0b01100, 0b00100, 0b01000], // ;
+    [0b00010, 0b00100, 0b01000, 0b10000, 0b01000, 0b00100, 0b00010], // <
+    [0b00000, 0b00000, 0b11111, 0b00000, 0b11111, 0b00000, 0b00000], // =
+    [0b01000, 0b00100, 0b00010, 0b00001, 0b00010, 0b00100, 0b01000], // >
+    [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b00000, 0b00100], // ?
+    [0b01110, 0b10001, 0b00001, 0b01101, 0b10101, 0b10101, 0b01110], // @
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001], // A
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110], // B
+    [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110], // C
+    [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100], // D
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111], // E
+    [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000], // F
+    [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111], // G
+    [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001], // H
+    [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // I
+    [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100], // J
+    [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001], // K
+    [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111], // L
+    [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001], // M
+    [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001], // N
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // O
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000], // P
+    [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101], // Q
+    [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001], // R
+    [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110], // S
+    [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // T
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110], // U
+    [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // V
+    [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010], // W
+    [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001], // X
+    [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100], // Y
+    [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111], // Z
+    [0b01110, 0b01000, 0b01000, 0b01000, 0b01000, 0b01000, 0b01110], // [
+    [0b00000, 0b10000, 0b01000, 0b00100, 0b00010, 0b00001, 0b00000], // \
+    [0b01110, 0b00010, 0b00010, 0b00010, 0b00010, 0b00010, 0b01110], // ]
+    [0b00100, 0b01010, 0b10001, 0b00000, 0b00000, 0b00000, 0b00000], // ^
+    [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b11111], // _
+    [0b01000, 0b00100, 0b00010, 0b00000, 0b00000, 0b00000, 0b00000], // `
+    [0b00000, 0b00000, 0b01110, 0b00001, 0b01111, 0b10001, 0b01111], // a
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b11110], // b
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b10000, 0b10001, 0b01110], // c
+    [0b00001, 0b00001, 0b01101, 0b10011, 0b10001, 0b10001, 0b01111], // d
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b11111, 0b10000, 0b01110], // e
+    [0b00110, 0b01001, 0b01000, 0b11100, 0b01000, 0b01000, 0b01000], // f
+    [0b00000, 0b01111, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // g
+    [0b10000, 0b10000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // h
+    [0b00100, 0b00000, 0b01100, 0b00100, 0b00100, 0b00100, 0b01110], // i
+    [0b00010, 0b00000, 0b00110, 0b00010, 0b00010, 0b10010, 0b01100], // j
+    [0b10000, 0b10000, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010], // k
+    [0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110], // l
+    [0b00000, 0b00000, 0b11010, 0b10101, 0b10101, 0b10001, 0b10001], // m
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10001, 0b10001, 0b10001], // n
+    [0b00000, 0b00000, 0b01110, 0b10001, 0b10001, 0b10001, 0b01110], // o
+    [0b00000, 0b00000, 0b11110, 0b10001, 0b11110, 0b10000, 0b10000], // p
+    [0b00000, 0b00000, 0b01101, 0b10011, 0b01111, 0b00001, 0b00001], // q
+    [0b00000, 0b00000, 0b10110, 0b11001, 0b10000, 0b10000, 0b10000], // r
+    [0b00000, 0b00000, 0b01110, 0b10000, 0b01110, 0b00001, 0b11110], // s
+    [0b01000, 0b01000, 0b11100, 0b01000, 0b01000, 0b01001, 0b00110], // t
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b10011, 0b01101], // u
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100], // v
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b10101, 0b10101, 0b01010], // w
+    [0b00000, 0b00000, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001], // x
+    [0b00000, 0b00000, 0b10001, 0b10001, 0b01111, 0b00001, 0b01110], // y
+    [0b00000, 0b00000, 0b11111, 0b00010, 0b00100, 0b01000, 0b11111], // z
+    [0b00010, 0b00100, 0b00100, 0b01000, 0b00100, 0b00100, 0b00010], // {
+    [0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100], // |
+    [0b01000, 0b00100, 0b00100, 0b00010, 0b00100, 0b00100, 0b01000], // }
+    [0b00000, 0b00000, 0b01000, 0b10101, 0b00010, 0b00000, 0b00000], // ~
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_printable_character_has_a_glyph() {
+        for c in ('!'..='~').chain('À'..='ÿ') {
+            assert_ne!(glyph(c), BLANK, "{:?}", c);
+        }
+        assert_eq!(glyph(' '), BLANK);
+    }
+
+    #[test]
+    fn lowercase_has_its_own_glyphs() {
+        for c in 'a'..='z' {
+            assert_ne!(glyph(c), glyph(c.to_ascii_uppercase()), "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn accents_sit_above_the_base_letter() {
+        assert_eq!(glyph('é')[2..], glyph('e')[2..]);
+        assert_eq!(glyph('é')[..2], Accent::Acute.rows());
+        assert_eq!(glyph('Ü')[..2], Accent::Diaeresis.rows());
+    }
+
+    #[test]
+    fn size_covers_the_longest_line() {
+        assert_eq!(text_size("AB\nC", 2), (11 * 2, 16 * 2));
+        assert_eq!(text_size("", 1), (0, GLYPH_HEIGHT));
+    }
+
+    #[test]
+    fn lines_are_aligned_on_x() {
+        let right_edge = |pixels: Vec<(i32, i32)>| pixels.iter().map(|&(x, _)| x).max();
+        let left_edge = |pixels: Vec<(i32, i32)>| pixels.iter().map(|&(x, _)| x).min();
+        assert_eq!(left_edge(text_pixels("L", 100, 0, 1, Align::Left)), Some(100));
+        assert_eq!(right_edge(text_pixels("HH", 100, 0, 1, Align::Right)), Some(99));
+        assert_eq!(left_edge(text_pixels("HH", 100, 0, 1, Align::Center)), Some(95));
+        // La deuxième ligne commence `LINE_HEIGHT` plus bas
+        let pixels = text_pixels("L\nL", 0, 0, 2, Align::Left);
+        assert_eq!(pixels.iter().map(|&(_, y)| y).max(), Some(((LINE_HEIGHT + GLYPH_HEIGHT - 1) * 2) as i32));
+    }
+}
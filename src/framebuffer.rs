@@ -0,0 +1,254 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::BlendMode;
+use crate::backend::Backend;
+
+// --- Image RGBA en mémoire ---
+
+/// Cible de dessin en pur Rust : 4 octets par pixel (R, G, B, A), lignes contiguës.
+/// Sert au terminal et aux tests du rendu, pixel par pixel.
+pub struct Framebuffer {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+    color: Color,
+    blend_mode: BlendMode,
+    scale: (f32, f32),
+}
+
+impl Framebuffer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![0; width as usize * height as usize * 4],
+            color: Color::RGBA(255, 255, 255, 255),
+            blend_mode: BlendMode::None,
+            scale: (1.0, 1.0),
+        }
+    }
+
+    /// Pixel de l'image (hors du facteur d'échelle) ; noir transparent hors de l'image
+    pub fn pixel(&self, x: i32, y: i32) -> Color {
+        match self.offset(x, y) {
+            Some(offset) => {
+                let p = &self.pixels[offset..offset + 4];
+                Color::RGBA(p[0], p[1], p[2], p[3])
+            },
+            None => Color::RGBA(0, 0, 0, 0),
+        }
+    }
+
+    fn offset(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            return None;
+        }
+        Some((y as usize * self.width as usize + x as usize) * 4)
+    }
+
+    /// Mélange la couleur courante dans un pixel de l'image, selon le mode de mélange
+    fn blend(&mut self, x: i32, y: i32) {
+        let Some(offset) = self.offset(x, y) else {
+            return;
+        };
+        let src = self.color;
+        let dst = &mut self.pixels[offset..offset + 4];
+        let alpha = src.a as u32;
+        let mix = |s: u8, d: u8| ((s as u32 * alpha + d as u32 * (255 - alpha)) / 255) as u8;
+        match self.blend_mode {
+            BlendMode::Blend => {
+                dst[0] = mix(src.r, dst[0]);
+                dst[1] = mix(src.g, dst[1]);
+                dst[2] = mix(src.b, dst[2]);
+                dst[3] = (alpha + dst[3] as u32 * (255 - alpha) / 255) as u8;
+            },
+            BlendMode::Add => {
+                for (d, s) in dst.iter_mut().zip([src.r, src.g, src.b]) {
+                    *d = (*d as u32 + s as u32 * alpha / 255).min(255) as u8;
+                }
+            },
+            BlendMode::Mod => {
+                for (d, s) in dst.iter_mut().zip([src.r, src.g, src.b]) {
+                    *d = (*d as u32 * s as u32 / 255) as u8;
+                }
+            },
+            BlendMode::Mul => {
+                for (d, s) in dst.iter_mut().zip([src.r, src.g, src.b]) {
+                    *d = ((s as u32 * *d as u32 + *d as u32 * (255 - alpha)) / 255).min(255) as u8;
+                }
+            },
+            BlendMode::None | BlendMode::Invalid => dst.copy_from_slice(&[src.r, src.g, src.b, src.a]),
+        }
+    }
+
+    /// Remplit la zone de l'image couverte par un rectangle en coordonnées agrandies
+    fn fill_scaled(&mut self, x: f32, y: f32, width: f32, height: f32) {
+        let (sx, sy) = self.scale;
+        let (left, top) = ((x * sx).floor() as i32, (y * sy).floor() as i32);
+        let (right, bottom) = (((x + width) * sx).floor() as i32, ((y + height) * sy).floor() as i32);
+        // Un pixel garde au moins un pixel de l'image, même réduit
+        for py in top.max(0)..bottom.max(top + 1).min(self.height as i32) {
+            for px in left.max(0)..right.max(left + 1).min(self.width as i32) {
+                self.blend(px, py);
+            }
+        }
+    }
+}
+
+impl Backend for Framebuffer {
+    fn set_draw_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.blend_mode = mode;
+    }
+
+    fn set_scale(&mut self, x: f32, y: f32) -> Result<(), String> {
+        if x <= 0.0 || y <= 0.0 {
+            return Err(format!("invalid scale {} x {}", x, y));
+        }
+        self.scale = (x, y);
+        Ok(())
+    }
+
+    fn output_size(&self) -> Result<(u32, u32), String> {
+        Ok((self.width, self.height))
+    }
+
+    fn clear(&mut self) {
+        let color = self.color;
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    fn draw_point(&mut self, (x, y): (i32, i32)) -> Result<(), String> {
+        self.fill_scaled(x as f32, y as f32, 1.0, 1.0);
+        Ok(())
+    }
+
+    /// Tracé de Bresenham
+    fn draw_line(&mut self, (x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> Result<(), String> {
+        let (dx, dy) = ((x2 - x1).abs(), -(y2 - y1).abs());
+        let (step_x, step_y) = (if x1 < x2 { 1 } else { -1 }, if y1 < y2 { 1 } else { -1 });
+        let (mut x, mut y) = (x1, y1);
+        let mut error = dx + dy;
+        loop {
+            self.draw_point((x, y))?;
+            if x == x2 && y == y2 {
+                return Ok(());
+            }
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right() - 1, rect.bottom() - 1);
+        self.draw_line((left, top), (right, top))?;
+        self.draw_line((left, bottom), (right, bottom))?;
+        // Côtés sans les coins, pour ne pas mélanger deux fois
+        if bottom - top > 1 {
+            self.draw_line((left, top + 1), (left, bottom - 1))?;
+            if right != left {
+                self.draw_line((right, top + 1), (right, bottom - 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        self.fill_scaled(rect.x() as f32, rect.y() as f32, rect.width() as f32, rect.height() as f32);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled(width: u32, height: u32, color: Color) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(width, height);
+        framebuffer.set_draw_color(color);
+        framebuffer.clear();
+        framebuffer
+    }
+
+    #[test]
+    fn clear_fills_every_pixel() {
+        let framebuffer = filled(3, 2, Color::RGB(10, 20, 30));
+        assert!(framebuffer.pixels.chunks_exact(4).all(|p| p == [10, 20, 30, 255]));
+    }
+
+    #[test]
+    fn line_includes_both_ends() {
+        let mut framebuffer = filled(8, 8, Color::BLACK);
+        framebuffer.set_draw_color(Color::WHITE);
+        framebuffer.draw_line((1, 1), (5, 3)).unwrap();
+        assert_eq!(framebuffer.pixel(1, 1), Color::WHITE);
+        assert_eq!(framebuffer.pixel(5, 3), Color::WHITE);
+        assert_eq!(framebuffer.pixel(3, 2), Color::WHITE);
+        assert_eq!(framebuffer.pixel(1, 3), Color::BLACK);
+    }
+
+    #[test]
+    fn drawing_outside_is_clipped() {
+        let mut framebuffer = filled(4, 4, Color::BLACK);
+        framebuffer.set_draw_color(Color::WHITE);
+        framebuffer.draw_line((-10, 1), (10, 1)).unwrap();
+        framebuffer.fill_rect(Rect::new(2, 2, 100, 100)).unwrap();
+        assert_eq!(framebuffer.pixel(0, 1), Color::WHITE);
+        assert_eq!(framebuffer.pixel(3, 3), Color::WHITE);
+        assert_eq!(framebuffer.pixel(1, 3), Color::BLACK);
+    }
+
+    #[test]
+    fn rect_outline_leaves_inside_untouched() {
+        let mut framebuffer = filled(6, 6, Color::BLACK);
+        framebuffer.set_draw_color(Color::WHITE);
+        framebuffer.draw_rect(Rect::new(1, 1, 4, 4)).unwrap();
+        assert_eq!(framebuffer.pixel(1, 1), Color::WHITE);
+        assert_eq!(framebuffer.pixel(4, 4), Color::WHITE);
+        assert_eq!(framebuffer.pixel(4, 2), Color::WHITE);
+        assert_eq!(framebuffer.pixel(2, 2), Color::BLACK);
+        assert_eq!(framebuffer.pixel(5, 5), Color::BLACK);
+    }
+
+    #[test]
+    fn blend_mixes_by_alpha() {
+        let mut framebuffer = filled(1, 1, Color::RGB(0, 0, 200));
+        framebuffer.set_blend_mode(BlendMode::Blend);
+        framebuffer.set_draw_color(Color::RGBA(255, 0, 0, 51));
+        framebuffer.draw_point((0, 0)).unwrap();
+        assert_eq!(framebuffer.pixel(0, 0), Color::RGBA(51, 0, 160, 255));
+
+        framebuffer.set_blend_mode(BlendMode::None);
+        framebuffer.draw_point((0, 0)).unwrap();
+        assert_eq!(framebuffer.pixel(0, 0), Color::RGBA(255, 0, 0, 51));
+    }
+
+    #[test]
+    fn scale_enlarges_points_and_rects() {
+        let mut framebuffer = filled(8, 8, Color::BLACK);
+        framebuffer.set_draw_color(Color::WHITE);
+        framebuffer.set_scale(2.0, 2.0).unwrap();
+        framebuffer.draw_point((1, 1)).unwrap();
+        assert_eq!(framebuffer.pixel(2, 2), Color::WHITE);
+        assert_eq!(framebuffer.pixel(3, 3), Color::WHITE);
+        assert_eq!(framebuffer.pixel(1, 1), Color::BLACK);
+        assert_eq!(framebuffer.pixel(4, 4), Color::BLACK);
+
+        framebuffer.fill_rect(Rect::new(2, 0, 2, 1)).unwrap();
+        assert_eq!(framebuffer.pixel(7, 1), Color::WHITE);
+        assert_eq!(framebuffer.pixel(7, 2), Color::BLACK);
+        assert!(framebuffer.set_scale(0.0, 1.0).is_err());
+    }
+}
@@ -1,21 +1,41 @@
 use sdl2::pixels::Color;
 use rand::Rng;
-use rand::rngs::ThreadRng;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 use std::ops::{Add, Sub, Mul, Div, AddAssign, SubAssign, MulAssign};
+use crate::config::{ConfigError, ConfigWatcher, max_ball_radius};
+use crate::rng::SimRng;
+use crate::history::History;
+use crate::console::Console;
+use crate::gesture::GestureRecognizer;
+use crate::keymap::Keymap;
+use crate::recorder::Recorder;
+use crate::panel::Panel;
+use crate::params::PARAMS;
+use crate::spawn::{SpawnPolicy, SpawnPolicyKind, SpawnContext, SpawnRequest, EscapedBall, OverflowPolicy};
 
 // Constantes précalculées pour éviter les appels répétés
 const PI: f32 = std::f32::consts::PI;
 const TWO_PI: f32 = 2.0 * PI;
 pub const TRAIL_LENGTH: usize = 36; // Longueur de la traînée (triplée)
+pub const TICKS_PER_SECOND: f32 = 60.0; // Fréquence du pas fixe de simulation
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+const SPAWN_ATTEMPTS: usize = 30;      // Tirages avant de se rabattre sur le meilleur candidat
+pub const MAX_PENDING_SPAWNS: usize = 1000; // Taille maximale de la file d'attente d'apparitions
+const DYNAMIC_CAP_RAISE_FPS: u32 = 58;  // Au-dessus : le plafond dynamique monte
+const DYNAMIC_CAP_LOWER_FPS: u32 = 50;  // En dessous : le plafond dynamique redescend
+pub const MIN_ZOOM: f32 = 0.5;
+pub const MAX_ZOOM: f32 = 3.0;
 
 // --- Gravity ---
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum GravityMode {
     Centripetal,
     Vertical,
 }
 
 // --- Config ---
+#[derive(Clone, PartialEq)]
 pub struct Config {
     pub screen_width: u32,
     pub sim_width: u32,
@@ -30,6 +50,16 @@ pub struct Config {
     pub ball_radius: f32,
     pub max_velocity: f32, // Limite de vitesse pour éviter le tunneling
     pub grid_cell_size: f32, // Taille des cellules de la grille spatiale
+    pub drag_stiffness: f32, // Raideur du ressort de saisie à la souris
+    pub drag_damping: f32,   // Amortissement du ressort de saisie
+    pub emitters: Vec<Emitter>, // Émetteurs présents au démarrage
+    pub spawn_policy: SpawnPolicyKind, // Règle de remplacement des balles sorties
+    pub overflow_policy: OverflowPolicy, // Comportement une fois max_balls atteint
+    pub bounciness: f32,
+    pub friction: f32,
+    pub gravity_mode: GravityMode,
+    pub gravity_angle: f32, // Direction de la gravité verticale (0 = vers le bas)
+    pub balls_to_spawn: u32, // Balles créées pour chaque balle sortie
 }
 
 impl Default for Config {
@@ -48,12 +78,23 @@ impl Default for Config {
             ball_radius: 15.0,
             max_velocity: 15.0,
             grid_cell_size: 40.0,
+            drag_stiffness: 0.08,
+            drag_damping: 0.15,
+            // Par exemple : vec![Emitter::fountain(Vector2D { x: 300.0, y: 400.0 })]
+            emitters: Vec::new(),
+            spawn_policy: SpawnPolicyKind::Multiplier,
+            overflow_policy: OverflowPolicy::Drop,
+            bounciness: 0.98,  // Encore plus de rebond
+            friction: 0.999,   // Très peu de friction
+            gravity_mode: GravityMode::Vertical,
+            gravity_angle: 0.0,
+            balls_to_spawn: 2,
         }
     }
 }
 
 
-#[derive(Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct Vector2D {
     pub x: f32,
     pub y: f32,
@@ -131,7 +172,9 @@ impl Vector2D {
     }
 }
 
+#[derive(Clone)]
 pub struct Ball {
+    pub id: u64,
     pub position: Vector2D,
     pub old_position: Vector2D,
     pub trail: Vec<Vector2D>,   // Historique des positions pour la traînée
@@ -143,16 +186,162 @@ pub struct Ball {
     pub color: Color,
 }
 
+// --- Emitters ---
+
+pub const MAX_EMITTER_RATE: f32 = 1000.0; // Balles par seconde
+const MAX_EMITS_PER_TICK: f32 = 17.0;     // Débit maximal ramené à un tick, arrondi au-dessus
+
+/// Émetteur de balles : lance des balles dans un cône de direction à débit constant
+#[derive(Clone, PartialEq)]
+pub struct Emitter {
+    pub position: Vector2D,
+    pub direction: f32,   // Angle de l'axe du cône (radians)
+    pub spread: f32,      // Demi-ouverture du cône (radians)
+    pub speed_min: f32,
+    pub speed_max: f32,
+    pub rate: f32,        // Balles par seconde
+    pub accumulator: f32, // Fraction de balle en attente d'émission
+}
+
+impl Emitter {
+    pub fn new(position: Vector2D, direction: f32, spread: f32, speed_min: f32, speed_max: f32, rate: f32) -> Self {
+        Self { position, direction, spread, speed_min, speed_max, rate, accumulator: 0.0 }
+    }
+
+    /// Fontaine verticale, par exemple au centre de l'anneau
+    pub fn fountain(position: Vector2D) -> Self {
+        Self::new(position, -PI / 2.0, 0.3, 4.0, 7.0, 2.0)
+    }
+
+    /// Tuyau étroit et rapide visant un point, par exemple l'ouverture de l'anneau
+    pub fn hose(position: Vector2D, target: Vector2D) -> Self {
+        let aim = target - position;
+        Self::new(position, aim.y.atan2(aim.x), 0.05, 8.0, 10.0, 4.0)
+    }
+}
+
+/// Grille spatiale uniforme : indices des balles rangés par cellule
+pub struct SpatialGrid {
+    cell_size: f32,
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialGrid {
+    pub fn new(config: &Config) -> Self {
+        let cell_size = config.grid_cell_size;
+        let width = (config.sim_width as f32 / cell_size).ceil() as usize + 1;
+        let height = (config.screen_height as f32 / cell_size).ceil() as usize + 1;
+        Self { cell_size, width, height, cells: vec![Vec::new(); width * height] }
+    }
+
+    pub fn build(config: &Config, balls: &[Ball]) -> Self {
+        let mut grid = Self::new(config);
+        for (i, ball) in balls.iter().enumerate() {
+            grid.insert(i, ball.position);
+        }
+        grid
+    }
+
+    fn cell_coords(&self, position: Vector2D) -> (usize, usize) {
+        let cx = ((position.x / self.cell_size) as usize).min(self.width - 1);
+        let cy = ((position.y / self.cell_size) as usize).min(self.height - 1);
+        (cx, cy)
+    }
+
+    pub fn insert(&mut self, index: usize, position: Vector2D) {
+        let (cx, cy) = self.cell_coords(position);
+        self.cells[cy * self.width + cx].push(index);
+    }
+
+    /// Distance libre entre `position` et la surface de la balle la plus proche,
+    /// plafonnée à `reach` (seules les cellules à portée sont examinées)
+    pub fn clearance(&self, balls: &[Ball], position: Vector2D, reach: f32) -> f32 {
+        let offset = Vector2D { x: reach, y: reach };
+        let (min_x, min_y) = self.cell_coords(position - offset);
+        let (max_x, max_y) = self.cell_coords(position + offset);
+        let mut clearance = reach;
+        for cy in min_y..=max_y {
+            for cx in min_x..=max_x {
+                for &i in &self.cells[cy * self.width + cx] {
+                    let ball = &balls[i];
+                    clearance = clearance.min((ball.position - position).length() - ball.radius);
+                }
+            }
+        }
+        clearance
+    }
+}
+
+// --- Time control ---
+
+pub const MIN_SPEED_EXPONENT: i32 = -4; // 1/16x
+pub const MAX_SPEED_EXPONENT: i32 = 6;  // 64x
+
+/// Pause, pas à pas et multiplicateur de vitesse (puissance de deux)
+pub struct SimClock {
+    pub paused: bool,
+    pub pending_steps: u32,   // Pas demandés un par un pendant la pause
+    pub speed_exponent: i32,  // Multiplicateur = 2^speed_exponent
+}
+
+impl SimClock {
+    pub fn time_scale(&self) -> f64 {
+        2f64.powi(self.speed_exponent)
+    }
+
+    /// Texte affiché dans le HUD : `1X`, `4X`, `1/16X`
+    pub fn speed_label(&self) -> String {
+        if self.speed_exponent >= 0 {
+            format!("{}X", 1u32 << self.speed_exponent)
+        } else {
+            format!("1/{}X", 1u32 << -self.speed_exponent)
+        }
+    }
+}
+
+/// Message temporaire affiché en bas de la simulation
+pub struct Toast {
+    pub message: String,
+    pub shown_at: Instant,
+}
+
+/// Balle saisie à la souris, reliée au curseur par un ressort
+pub struct HeldBall {
+    pub id: u64,
+    pub target: Vector2D,          // Position actuelle du curseur
+    pub previous_target: Vector2D, // Position du curseur au tick précédent
+    pub cursor_velocity: Vector2D, // Vitesse lissée du curseur (px/tick)
+}
+
 pub struct World {
     pub balls: Vec<Ball>,
+    pub next_ball_id: u64,
+    pub held_ball: Option<HeldBall>,
+    pub emitters: Vec<Emitter>,
+    pub emitter_placement: Option<(Vector2D, Vector2D)>, // Émetteur en cours de placement (origine, visée)
     pub circle_center: Vector2D, // Centre du cercle précalculé
     pub circle_angle: f32,
-    pub bounciness: f32,
-    pub friction: f32,
     pub config: Config,
-    pub gravity_mode: GravityMode,
-    pub balls_to_spawn: u32,
-    pub rng: ThreadRng, // RNG réutilisable
+    pub config_errors: Vec<ConfigError>, // Erreurs de la dernière configuration refusée
+    pub toast: Option<Toast>,
+    pub spawn_policy: Box<dyn SpawnPolicy>,
+    pub pending_spawns: VecDeque<SpawnRequest>, // Apparitions en attente d'une place
+    pub ball_cap: usize, // Plafond effectif (max_balls, ou plus avec le plafond dynamique)
+    pub rng: SimRng, // RNG réutilisable, dont l'état est sauvegardé avec le monde
+    pub seed: u64, // Graine du RNG au début de la simulation
+    pub tick: u64, // Nombre de pas de simulation depuis le début
+    pub history: History, // Instantanés récents pour le retour en arrière
+    pub clock: SimClock,
+    pub panel: Panel, // Panneau de réglages du HUD
+    pub console: Console,
+    pub keymap: Keymap,
+    pub help_open: bool, // Aide des raccourcis (F1)
+    pub gestures: GestureRecognizer,
+    pub zoom: f32, // Agrandissement de la vue autour du centre de l'anneau
+    pub recorder: Option<Recorder>, // Clip GIF en cours d'enregistrement
+    pub screenshot_requested: bool, // Capture d'écran à prendre après le prochain rendu
     // HUD Stats
     pub fps: u32,
     pub wall_collisions: u32,
@@ -161,23 +350,38 @@ pub struct World {
     pub total_ball_collisions: u64,
 }
 
-pub fn initialize_world(config: Config) -> World {
-    let mut rng = rand::thread_rng();
-    let circle_center = Vector2D { 
-        x: config.sim_width as f32 / 2.0, 
-        y: config.screen_height as f32 / 2.0 
-    };
-    let balls = vec![create_random_ball_with_rng(&config, &mut rng, circle_center)];
+pub fn initialize_world(config: Config, mut rng: SimRng) -> World {
+    let seed = rng.state;
+    let circle_center = circle_center_for(&config);
+    let grid = SpatialGrid::new(&config);
+    let balls = vec![create_random_ball_with_rng(&config, &mut rng, circle_center, 0, &grid, &[])];
     World {
         balls,
+        next_ball_id: 1,
+        held_ball: None,
+        emitters: config.emitters.clone(),
+        spawn_policy: config.spawn_policy.build(),
+        pending_spawns: VecDeque::new(),
+        ball_cap: config.max_balls,
+        emitter_placement: None,
         circle_center,
         circle_angle: 0.0,
-        bounciness: 0.98,  // Encore plus de rebond
-        friction: 0.999,   // Très peu de friction
         config,
-        gravity_mode: GravityMode::Vertical,
-        balls_to_spawn: 2,
+        config_errors: Vec::new(),
+        toast: None,
         rng,
+        seed,
+        tick: 0,
+        history: History::new(),
+        clock: SimClock { paused: false, pending_steps: 0, speed_exponent: 0 },
+        panel: Panel::new(),
+        console: Console::new(),
+        keymap: Keymap::default(),
+        help_open: false,
+        gestures: GestureRecognizer::new(),
+        zoom: 1.0,
+        recorder: None,
+        screenshot_requested: false,
         fps: 0,
         wall_collisions: 0,
         ball_collisions: 0,
@@ -186,24 +390,78 @@ pub fn initialize_world(config: Config) -> World {
     }
 }
 
-fn create_random_ball_with_rng(config: &Config, rng: &mut ThreadRng, circle_center: Vector2D) -> Ball {
+/// Centre de l'anneau : milieu de la zone de simulation
+pub fn circle_center_for(config: &Config) -> Vector2D {
+    Vector2D { 
+        x: config.sim_width as f32 / 2.0, 
+        y: config.screen_height as f32 / 2.0 
+    }
+}
+
+fn create_random_ball_with_rng(
+    config: &Config,
+    rng: &mut SimRng,
+    circle_center: Vector2D,
+    id: u64,
+    grid: &SpatialGrid,
+    balls: &[Ball],
+) -> Ball {
+    let position = find_spawn_position(config, rng, circle_center, grid, balls);
+    let velocity = random_velocity(rng);
+    create_ball_at(config, rng, position, velocity, id)
+}
+
+/// Cherche une position libre dans le cercle par échantillonnage avec rejet.
+/// Si l'anneau est trop encombré, garde le candidat le plus dégagé.
+fn find_spawn_position(
+    config: &Config,
+    rng: &mut SimRng,
+    circle_center: Vector2D,
+    grid: &SpatialGrid,
+    balls: &[Ball],
+) -> Vector2D {
+    let radius = config.ball_radius;
+    let max_dist = (config.circle_radius - radius - 10.0).max(0.0); // Marge de sécurité
+    // Portée de recherche : notre rayon plus le rayon maximal d'une balle (demi-cellule)
+    let reach = radius + config.grid_cell_size * 0.5;
+
+    let mut best = circle_center;
+    let mut best_clearance = f32::MIN;
+    for _ in 0..SPAWN_ATTEMPTS {
+        // Distribution uniforme en surface : la racine compense la densité près du centre
+        let angle = rng.gen_range(0.0..TWO_PI);
+        let dist = max_dist * rng.gen_range(0.0f32..=1.0).sqrt();
+        let candidate = Vector2D {
+            x: circle_center.x + angle.cos() * dist,
+            y: circle_center.y + angle.sin() * dist,
+        };
+
+        let clearance = grid.clearance(balls, candidate, reach);
+        if clearance >= radius {
+            return candidate;
+        }
+        if clearance > best_clearance {
+            best = candidate;
+            best_clearance = clearance;
+        }
+    }
+    best
+}
+
+fn random_velocity(rng: &mut SimRng) -> Vector2D {
+    Vector2D { x: rng.gen_range(-2.0..2.0), y: rng.gen_range(-2.0..2.0) }
+}
+
+/// Crée une balle à une position et une vitesse données (rotation et couleur aléatoires)
+fn create_ball_at(config: &Config, rng: &mut SimRng, position: Vector2D, velocity: Vector2D, id: u64) -> Ball {
     let radius = config.ball_radius;
-    
-    // Spawn dans le cercle avec distribution uniforme
-    let angle = rng.gen_range(0.0..TWO_PI);
-    let max_dist = config.circle_radius - radius - 10.0; // Marge de sécurité
-    let dist = rng.gen_range(0.0..max_dist).max(0.0);
-
-    let position = Vector2D {
-        x: circle_center.x + angle.cos() * dist,
-        y: circle_center.y + angle.sin() * dist,
-    };
 
     Ball {
+        id,
         position,
         old_position: position,
         trail: Vec::with_capacity(TRAIL_LENGTH),
-        velocity: Vector2D { x: rng.gen_range(-2.0..2.0), y: rng.gen_range(-2.0..2.0) },
+        velocity,
         acceleration: Vector2D::default(),
         radius,
         rotation: rng.gen_range(0.0..TWO_PI),
@@ -214,6 +472,7 @@ fn create_random_ball_with_rng(config: &Config, rng: &mut ThreadRng, circle_cent
 
 pub fn update_world(world: &mut World) {
     // Reset counters for the new frame
+    world.tick += 1;
     world.wall_collisions = 0;
     world.ball_collisions = 0;
 
@@ -223,23 +482,39 @@ pub fn update_world(world: &mut World) {
     let circle_center = world.circle_center;
     let circle_radius = world.config.circle_radius;
     let ball_radius = world.config.ball_radius;
-    let inner_radius = circle_radius - ball_radius;
-    let inner_radius_sq = inner_radius * inner_radius;
+
+    // Suivre la vitesse du curseur pour pouvoir lancer la balle au relâchement
+    let held_id = world.held_ball.as_mut().map(|held| {
+        let delta = held.target - held.previous_target;
+        held.cursor_velocity = held.cursor_velocity * 0.5 + delta * 0.5;
+        held.previous_target = held.target;
+        held.id
+    });
 
     // Ball physics and collision
     for ball in &mut world.balls {
-        match world.gravity_mode {
-            GravityMode::Centripetal => {
-                let to_center = circle_center - ball.position;
-                ball.acceleration += to_center.normalized() * world.config.centripetal_gravity;
-            },
-            GravityMode::Vertical => {
-                ball.acceleration.y += world.config.vertical_gravity;
+        if Some(ball.id) == held_id {
+            // Ressort amorti vers le curseur, sans gravité : la balle reste soumise aux collisions
+            if let Some(held) = &world.held_ball {
+                let stretch = held.target - ball.position;
+                ball.acceleration += stretch * world.config.drag_stiffness;
+                ball.acceleration -= (ball.velocity - held.cursor_velocity) * world.config.drag_damping;
+            }
+        } else {
+            match world.config.gravity_mode {
+                GravityMode::Centripetal => {
+                    let to_center = circle_center - ball.position;
+                    ball.acceleration += to_center.normalized() * world.config.centripetal_gravity;
+                },
+                GravityMode::Vertical => {
+                    let angle = world.config.gravity_angle;
+                    ball.acceleration += Vector2D { x: angle.sin(), y: angle.cos() } * world.config.vertical_gravity;
+                }
             }
         }
 
         ball.velocity += ball.acceleration;
-        ball.velocity *= world.friction;
+        ball.velocity *= world.config.friction;
         
         // Limiter la vitesse pour éviter le tunneling
         let speed_sq = ball.velocity.length_squared();
@@ -262,10 +537,12 @@ pub fn update_world(world: &mut World) {
         ball.rotation = (ball.rotation + ball.angular_velocity) % TWO_PI;
         ball.angular_velocity *= 0.995; // Friction angulaire légère
 
+        // Rayon intérieur propre à chaque balle (les balles fusionnées sont plus grosses)
+        let inner_radius = circle_radius - ball.radius;
         let to_ball = ball.position - circle_center;
         let dist_sq = to_ball.length_squared();
 
-        if dist_sq > inner_radius_sq {
+        if dist_sq > inner_radius * inner_radius {
             let ball_angle = to_ball.y.atan2(to_ball.x);
             let gap_start = world.circle_angle - world.config.circle_gap_angle * 0.5;
             let gap_end = world.circle_angle + world.config.circle_gap_angle * 0.5;
@@ -292,7 +569,7 @@ pub fn update_world(world: &mut World) {
                 
                 // Rebond normal
                 let normal_velocity = ball.velocity.dot(normal);
-                ball.velocity -= normal * (2.0 * normal_velocity * world.bounciness);
+                ball.velocity -= normal * (2.0 * normal_velocity * world.config.bounciness);
                 
                 // Transférer une partie de la vitesse tangentielle en rotation
                 ball.angular_velocity += tangent_velocity * 0.05 / ball.radius;
@@ -302,45 +579,54 @@ pub fn update_world(world: &mut World) {
 
     // Remove balls that are far outside the circle (fallen)
     let removal_threshold_sq = (circle_radius + 50.0) * (circle_radius + 50.0);
-    let initial_ball_count = world.balls.len();
+    let mut escaped = Vec::new();
     world.balls.retain(|ball| {
-        (ball.position - circle_center).length_squared() < removal_threshold_sq
+        let keep = Some(ball.id) == held_id
+            || (ball.position - circle_center).length_squared() < removal_threshold_sq;
+        if !keep {
+            escaped.push(EscapedBall { position: ball.position, velocity: ball.velocity });
+        }
+        keep
     });
-    let fallen_balls_count = initial_ball_count - world.balls.len();
-
-    // Spawn new balls - chaque balle tombée génère balls_to_spawn nouvelles balles
-    if fallen_balls_count > 0 {
-        let balls_to_add = (fallen_balls_count * world.balls_to_spawn as usize)
-            .min(world.config.max_balls.saturating_sub(world.balls.len()));
-        world.balls.reserve(balls_to_add);
-        for _ in 0..balls_to_add {
-            let new_ball = create_random_ball_with_rng(&world.config, &mut world.rng, circle_center);
-            world.balls.push(new_ball);
+
+    // Spawn new balls - la politique d'apparition décide du nombre et du placement
+    let spawn_context = SpawnContext {
+        ball_count: world.balls.len(),
+        pending_count: world.pending_spawns.len(),
+        balls_to_spawn: world.config.balls_to_spawn,
+        circle_center,
+        spawn_radius: circle_radius - ball_radius - 10.0,
+    };
+    let mut requests = world.spawn_policy.spawn(&escaped, &spawn_context);
+
+    // Émetteurs : le débit est accumulé à chaque tick, une balle sort par unité entière,
+    // dans la limite d'un tick au débit maximal. Leurs balles passent par la politique de
+    // dépassement comme les autres.
+    for emitter in &mut world.emitters {
+        emitter.accumulator += emitter.rate / TICKS_PER_SECOND;
+        let count = emitter.accumulator.floor().min(MAX_EMITS_PER_TICK);
+        emitter.accumulator = emitter.accumulator.fract();
+        for _ in 0..count as usize {
+            let angle = emitter.direction + world.rng.gen_range(-emitter.spread..=emitter.spread);
+            let speed = world.rng.gen_range(emitter.speed_min..=emitter.speed_max);
+            let velocity = Vector2D { x: angle.cos() * speed, y: angle.sin() * speed };
+            requests.push(SpawnRequest { position: Some(emitter.position), velocity: Some(velocity) });
         }
     }
+    apply_spawn_requests(world, requests, held_id);
 
     // Ball-to-ball collision avec grille spatiale
-    let cell_size = world.config.grid_cell_size;
-    let grid_width = (world.config.sim_width as f32 / cell_size).ceil() as usize + 1;
-    let grid_height = (world.config.screen_height as f32 / cell_size).ceil() as usize + 1;
-    let grid_size = grid_width * grid_height;
-    
-    // Construire la grille
-    let mut grid: Vec<Vec<usize>> = vec![Vec::new(); grid_size];
-    for (i, ball) in world.balls.iter().enumerate() {
-        let cx = ((ball.position.x / cell_size) as usize).min(grid_width - 1);
-        let cy = ((ball.position.y / cell_size) as usize).min(grid_height - 1);
-        grid[cy * grid_width + cx].push(i);
-    }
+    let grid = SpatialGrid::build(&world.config, &world.balls);
+    let (grid_width, grid_height) = (grid.width, grid.height);
     
     // Vérifier les collisions seulement entre balles dans les cellules voisines
-    let bounciness = world.bounciness;
+    let bounciness = world.config.bounciness;
     let restitution = bounciness.sqrt();
     
     for cy in 0..grid_height {
         for cx in 0..grid_width {
             let cell_idx = cy * grid_width + cx;
-            let cell_balls = &grid[cell_idx];
+            let cell_balls = &grid.cells[cell_idx];
             
             // Collisions dans la même cellule
             for i in 0..cell_balls.len() {
@@ -367,7 +653,7 @@ pub fn update_world(world: &mut World) {
                 if nx < grid_width && ny < grid_height {
                     let neighbor_idx = ny * grid_width + nx;
                     for &idx_a in cell_balls {
-                        for &idx_b in &grid[neighbor_idx] {
+                        for &idx_b in &grid.cells[neighbor_idx] {
                             if let Some(collision) = check_collision(&world.balls[idx_a], &world.balls[idx_b]) {
                                 world.ball_collisions += 1;
                                 world.total_ball_collisions += 1;
@@ -381,6 +667,130 @@ pub fn update_world(world: &mut World) {
     }
 }
 
+/// Fait apparaître les balles demandées en respectant le plafond selon la politique de dépassement
+fn apply_spawn_requests(world: &mut World, requests: Vec<SpawnRequest>, held_id: Option<u64>) {
+    let overflow_policy = world.config.overflow_policy;
+    world.pending_spawns.extend(requests);
+
+    match overflow_policy {
+        OverflowPolicy::DynamicCap { ceiling } => adjust_ball_cap(world, ceiling),
+        _ => world.ball_cap = world.config.max_balls,
+    }
+
+    // Faire de la place si la politique le permet
+    let overflow = (world.balls.len() + world.pending_spawns.len()).saturating_sub(world.ball_cap);
+    match overflow_policy {
+        OverflowPolicy::EvictOldest => evict_oldest(world, overflow, held_id),
+        OverflowPolicy::MergeSmallest => {
+            for _ in 0..overflow {
+                if !merge_smallest(world, held_id) {
+                    break;
+                }
+            }
+        },
+        _ => {}
+    }
+
+    let count = world.ball_cap.saturating_sub(world.balls.len()).min(world.pending_spawns.len());
+    world.balls.reserve(count);
+    let mut grid = SpatialGrid::build(&world.config, &world.balls);
+    for _ in 0..count {
+        if let Some(request) = world.pending_spawns.pop_front() {
+            spawn_from_request(world, request, &mut grid);
+        }
+    }
+
+    if overflow_policy.keeps_pending() {
+        world.pending_spawns.truncate(MAX_PENDING_SPAWNS);
+    } else {
+        world.pending_spawns.clear();
+    }
+}
+
+/// Crée une balle à partir d'une demande, au hasard pour ce qui n'est pas précisé.
+/// La grille est tenue à jour pour que les apparitions suivantes évitent cette balle.
+fn spawn_from_request(world: &mut World, request: SpawnRequest, grid: &mut SpatialGrid) {
+    let id = world.next_ball_id;
+    world.next_ball_id += 1;
+    let mut new_ball = match request.position {
+        Some(position) => {
+            // Léger décalage pour que les balles superposées puissent se séparer
+            let jitter = Vector2D { x: world.rng.gen_range(-1.0..1.0), y: world.rng.gen_range(-1.0..1.0) };
+            let velocity = random_velocity(&mut world.rng);
+            create_ball_at(&world.config, &mut world.rng, position + jitter, velocity, id)
+        },
+        None => create_random_ball_with_rng(&world.config, &mut world.rng, world.circle_center, id, grid, &world.balls),
+    };
+    if let Some(velocity) = request.velocity {
+        new_ball.velocity = velocity;
+    }
+    grid.insert(world.balls.len(), new_ball.position);
+    world.balls.push(new_ball);
+}
+
+/// Monte le plafond quand le rendu tient la cadence et que des balles attendent,
+/// le redescend (jamais sous max_balls) quand il décroche
+fn adjust_ball_cap(world: &mut World, ceiling: usize) {
+    if world.fps == 0 {
+        return; // Cadence pas encore mesurée
+    }
+    let floor = world.config.max_balls;
+    if world.fps >= DYNAMIC_CAP_RAISE_FPS && !world.pending_spawns.is_empty() {
+        world.ball_cap = (world.ball_cap + 1).min(ceiling.max(floor));
+    } else if world.fps < DYNAMIC_CAP_LOWER_FPS {
+        world.ball_cap = world.ball_cap.saturating_sub(1).max(floor);
+    }
+}
+
+/// Retire les `count` balles les plus anciennes (les balles sont rangées par ordre d'apparition)
+fn evict_oldest(world: &mut World, count: usize, held_id: Option<u64>) {
+    let mut remaining = count;
+    world.balls.retain(|ball| {
+        if remaining > 0 && Some(ball.id) != held_id {
+            remaining -= 1;
+            false
+        } else {
+            true
+        }
+    });
+}
+
+/// Fusionne les deux plus petites balles en conservant masse et quantité de mouvement.
+/// Le rayon obtenu doit rester sous la demi-cellule pour que la grille détecte toutes les
+/// collisions : une fusion plus grosse est refusée plutôt que de perdre de la masse.
+/// Retourne `false` s'il n'y a plus rien à fusionner ; les apparitions en trop sont alors perdues.
+fn merge_smallest(world: &mut World, held_id: Option<u64>) -> bool {
+    let mut candidates: Vec<usize> = (0..world.balls.len())
+        .filter(|&i| Some(world.balls[i].id) != held_id)
+        .collect();
+    if candidates.len() < 2 {
+        return false;
+    }
+    candidates.sort_by(|&a, &b| world.balls[a].radius.total_cmp(&world.balls[b].radius));
+    let (m1, m2) = (world.balls[candidates[0]].radius.powi(2), world.balls[candidates[1]].radius.powi(2));
+    // Les deux plus petites donnent la plus petite fusion possible
+    if (m1 + m2).sqrt() > max_ball_radius(&world.config) {
+        return false;
+    }
+    let keep = candidates[0].min(candidates[1]);
+    let absorbed = world.balls.remove(candidates[0].max(candidates[1]));
+
+    let ball = &mut world.balls[keep];
+    let m1 = ball.radius * ball.radius;
+    let m2 = absorbed.radius * absorbed.radius;
+    let total = m1 + m2;
+    ball.position = (ball.position * m1 + absorbed.position * m2) / total;
+    ball.old_position = ball.position;
+    ball.velocity = (ball.velocity * m1 + absorbed.velocity * m2) / total;
+    ball.radius = total.sqrt();
+    ball.color = Color::RGB(
+        ((ball.color.r as u16 + absorbed.color.r as u16) / 2) as u8,
+        ((ball.color.g as u16 + absorbed.color.g as u16) / 2) as u8,
+        ((ball.color.b as u16 + absorbed.color.b as u16) / 2) as u8,
+    );
+    true
+}
+
 /// Données de collision
 struct CollisionData {
     normal: Vector2D,
@@ -439,13 +849,428 @@ fn resolve_collision(balls: &mut [Ball], idx_a: usize, idx_b: usize, collision:
     balls[idx_b].velocity = (balls[idx_b].velocity - normal * (v2_dot_normal - v2_prime_dot)) * restitution;
 }
 
-// Fonctions pour modifier le nombre de balles à générer
-pub fn increase_balls_to_spawn(world: &mut World) {
-    world.balls_to_spawn += 1;
+// Contrôle du temps
+pub fn toggle_pause(world: &mut World) {
+    world.clock.paused = !world.clock.paused;
+}
+
+/// Avance d'un seul pas ; met la simulation en pause si elle tournait
+pub fn request_single_step(world: &mut World) {
+    world.clock.paused = true;
+    world.clock.pending_steps += 1;
+}
+
+pub fn speed_up(world: &mut World) {
+    world.clock.speed_exponent = (world.clock.speed_exponent + 1).min(MAX_SPEED_EXPONENT);
+}
+
+pub fn slow_down(world: &mut World) {
+    world.clock.speed_exponent = (world.clock.speed_exponent - 1).max(MIN_SPEED_EXPONENT);
+}
+
+// --- Vue ---
+
+/// Position à l'écran d'un point de la simulation
+pub fn sim_to_screen(world: &World, point: Vector2D) -> Vector2D {
+    world.circle_center + (point - world.circle_center) * world.zoom
+}
+
+/// Point de la simulation sous une position de l'écran (souris, doigt)
+pub fn screen_to_sim(world: &World, point: Vector2D) -> Vector2D {
+    world.circle_center + (point - world.circle_center) / world.zoom
+}
+
+/// Agrandit la vue ; la simulation elle-même n'en dépend pas
+pub fn zoom_view(world: &mut World, factor: f32) {
+    world.zoom = (world.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+}
+
+/// Tourne l'anneau, en plus de sa rotation continue
+pub fn rotate_ring(world: &mut World, angle: f32) {
+    world.circle_angle = (world.circle_angle + angle) % TWO_PI;
+}
+
+/// Applique une nouvelle configuration après validation. Si elle est invalide,
+/// l'ancienne reste active et les erreurs sont affichées par-dessus la simulation.
+pub fn apply_config(world: &mut World, config: Config) -> bool {
+    if let Err(errors) = config.validate() {
+        world.config_errors = errors;
+        return false;
+    }
+    if config.spawn_policy != world.config.spawn_policy {
+        world.spawn_policy = config.spawn_policy.build();
+    }
+    world.config = config;
+    world.config_errors.clear();
+    true
+}
+
+/// Applique à chaud une configuration relue depuis le disque par-dessus la configuration en cours
+/// (voir `ConfigWatcher`). Les paramètres qui ne peuvent pas changer sans redémarrer
+/// (taille de la fenêtre) sont conservés et retournés.
+pub fn apply_live_config(world: &mut World, mut config: Config) -> Vec<&'static str> {
+    let mut restart_fields = Vec::new();
+    for param in PARAMS.iter().filter(|param| !param.live) {
+        let current = (param.get)(&world.config);
+        if (param.get)(&config) != current {
+            restart_fields.push(param.name);
+            (param.set)(&mut config, current);
+        }
+    }
+
+    // Les émetteurs du fichier remplacent ceux en place seulement s'ils ont changé
+    let emitters_changed = config.emitters != world.config.emitters;
+    if apply_config(world, config) && emitters_changed {
+        world.emitters = world.config.emitters.clone();
+    }
+    restart_fields
 }
 
-pub fn decrease_balls_to_spawn(world: &mut World) {
-    if world.balls_to_spawn > 1 {
-        world.balls_to_spawn -= 1;
+/// Rechargement à chaud du fichier surveillé : en cas d'erreur, la dernière configuration valide reste active
+pub fn reload_config(world: &mut World, watcher: &mut ConfigWatcher) {
+    match watcher.poll(&world.config) {
+        Some(Ok(config)) => {
+            let restart_fields = apply_live_config(world, config);
+            let message = if !world.config_errors.is_empty() {
+                "CONFIG INVALID, NOT APPLIED".to_string()
+            } else if !restart_fields.is_empty() {
+                format!("RESTART NEEDED: {}", restart_fields.join(", "))
+            } else {
+                "CONFIG RELOADED".to_string()
+            };
+            show_toast(world, message);
+        },
+        Some(Err(message)) => {
+            eprintln!("{}", message);
+            show_toast(world, format!("CONFIG ERROR: {}", message.lines().next().unwrap_or("")));
+        },
+        None => {},
+    }
+}
+
+/// Affiche un message temporaire
+pub fn show_toast(world: &mut World, message: String) {
+    world.toast = Some(Toast { message, shown_at: Instant::now() });
+}
+
+/// Passe à la politique d'apparition suivante
+pub fn cycle_spawn_policy(world: &mut World) {
+    let mut config = world.config.clone();
+    config.spawn_policy = config.spawn_policy.next();
+    apply_config(world, config);
+}
+
+/// Passe à la politique de dépassement suivante
+pub fn cycle_overflow_policy(world: &mut World) {
+    let mut config = world.config.clone();
+    config.overflow_policy = config.overflow_policy.next();
+    apply_config(world, config);
+}
+
+// --- Saisie à la souris ---
+
+/// Retourne l'index de la balle sous le point donné (la plus haute à l'écran en priorité)
+pub fn pick_ball(world: &World, point: Vector2D) -> Option<usize> {
+    world.balls.iter().rposition(|ball| {
+        (ball.position - point).length_squared() <= ball.radius * ball.radius
+    })
+}
+
+/// Saisit la balle sous le curseur, s'il y en a une
+pub fn grab_ball(world: &mut World, point: Vector2D) {
+    if let Some(index) = pick_ball(world, point) {
+        world.held_ball = Some(HeldBall {
+            id: world.balls[index].id,
+            target: point,
+            previous_target: point,
+            cursor_velocity: Vector2D::default(),
+        });
+    }
+}
+
+/// Déplace la cible du ressort vers la nouvelle position du curseur
+pub fn drag_ball(world: &mut World, point: Vector2D) {
+    if let Some(held) = &mut world.held_ball {
+        held.target = point;
+    }
+}
+
+/// Relâche la balle en lui transmettant la vitesse du curseur
+pub fn release_ball(world: &mut World) {
+    if let Some(held) = world.held_ball.take()
+        && let Some(ball) = world.balls.iter_mut().find(|ball| ball.id == held.id)
+    {
+        ball.velocity = held.cursor_velocity;
+    }
+}
+
+/// Retourne la balle actuellement saisie et la cible du ressort
+pub fn held_ball(world: &World) -> Option<(&Ball, Vector2D)> {
+    let held = world.held_ball.as_ref()?;
+    world.balls.iter().find(|ball| ball.id == held.id).map(|ball| (ball, held.target))
+}
+
+// --- Apparition manuelle et émetteurs ---
+
+/// Rayon de sélection d'un émetteur à la souris
+const EMITTER_PICK_RADIUS: f32 = 10.0;
+
+/// Dépose une balle immobile sous le curseur, en respectant la politique de dépassement.
+/// Un point hors de l'anneau est ignoré : la balle sortirait aussitôt.
+pub fn drop_ball(world: &mut World, point: Vector2D) {
+    let inner_radius = world.config.circle_radius - world.config.ball_radius;
+    if (point - world.circle_center).length_squared() > inner_radius * inner_radius {
+        return;
+    }
+    let held_id = world.held_ball.as_ref().map(|held| held.id);
+    let request = SpawnRequest { position: Some(point), velocity: Some(Vector2D::default()) };
+    apply_spawn_requests(world, vec![request], held_id);
+}
+
+/// Demande `count` balles, à une position donnée ou au hasard dans l'anneau, en respectant
+/// la politique de dépassement. Retourne le nombre de balles créées pour cette demande :
+/// les apparitions déjà en attente passent avant elle.
+pub fn spawn_balls(world: &mut World, count: usize, position: Option<Vector2D>) -> u64 {
+    let first_id = world.next_ball_id;
+    let queued_before = world.pending_spawns.len() as u64;
+    let held_id = world.held_ball.as_ref().map(|held| held.id);
+    apply_spawn_requests(world, vec![SpawnRequest { position, velocity: None }; count], held_id);
+    (world.next_ball_id - first_id).saturating_sub(queued_before)
+}
+
+/// Retire toutes les balles et les apparitions en attente
+pub fn clear_balls(world: &mut World) {
+    world.balls.clear();
+    world.held_ball = None;
+    world.pending_spawns.clear();
+}
+
+/// Commence le placement d'un émetteur, ou supprime celui qui se trouve sous le curseur
+pub fn begin_emitter_placement(world: &mut World, point: Vector2D) {
+    let picked = world.emitters.iter().position(|emitter| {
+        (emitter.position - point).length_squared() <= EMITTER_PICK_RADIUS * EMITTER_PICK_RADIUS
+    });
+    match picked {
+        Some(index) => {
+            world.emitters.remove(index);
+        },
+        None => world.emitter_placement = Some((point, point)),
+    }
+}
+
+/// Met à jour la visée de l'émetteur en cours de placement
+pub fn aim_emitter_placement(world: &mut World, point: Vector2D) {
+    if let Some((_, aim)) = &mut world.emitter_placement {
+        *aim = point;
+    }
+}
+
+/// Termine le placement : un simple clic crée une fontaine, un glisser crée un tuyau
+/// visant le point de relâchement
+pub fn finish_emitter_placement(world: &mut World) {
+    if let Some((origin, aim)) = world.emitter_placement.take() {
+        let emitter = if (aim - origin).length_squared() < 16.0 {
+            Emitter::fountain(origin)
+        } else {
+            Emitter::hose(origin, aim)
+        };
+        world.emitters.push(emitter);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world() -> World {
+        let config = Config { max_balls: 3, overflow_policy: OverflowPolicy::MergeSmallest, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        world.balls.clear();
+        world
+    }
+
+    fn add_ball(world: &mut World, radius: f32) {
+        let mut ball = create_ball_at(&world.config, &mut world.rng, world.circle_center, Vector2D::default(), world.next_ball_id);
+        ball.radius = radius;
+        world.next_ball_id += 1;
+        world.balls.push(ball);
+    }
+
+    /// Monde à une seule balle immobile au centre, saisie à la souris
+    fn world_with_held_ball() -> World {
+        let mut world = world();
+        world.config.gravity_mode = GravityMode::Vertical;
+        add_ball(&mut world, 10.0);
+        let center = world.circle_center;
+        grab_ball(&mut world, center);
+        assert!(world.held_ball.is_some());
+        world
+    }
+
+    #[test]
+    fn held_ball_ignores_gravity() {
+        let mut world = world_with_held_ball();
+        add_ball(&mut world, 10.0);
+        world.balls[1].position.x += 100.0; // Hors de portée de la balle saisie
+        update_world(&mut world);
+        assert_eq!(world.balls[0].velocity, Vector2D::default());
+        assert!(world.balls[1].velocity.y > 0.0);
+    }
+
+    #[test]
+    fn held_ball_does_not_escape() {
+        let mut world = world_with_held_ball();
+        let outside = world.circle_center + Vector2D { x: world.config.circle_radius + 200.0, y: 0.0 };
+        world.balls[0].position = outside;
+        drag_ball(&mut world, outside);
+        update_world(&mut world);
+        assert_eq!(world.balls.len(), 1);
+        assert!(world.held_ball.is_some());
+    }
+
+    #[test]
+    fn release_flings_with_the_drag_motion() {
+        let mut world = world_with_held_ball();
+        let mut target = world.circle_center;
+        for _ in 0..20 {
+            target.x += 4.0;
+            drag_ball(&mut world, target);
+            update_world(&mut world);
+        }
+        release_ball(&mut world);
+        assert!(world.held_ball.is_none());
+        let velocity = world.balls[0].velocity;
+        assert!((velocity.x - 4.0).abs() < 0.01 && velocity.y.abs() < 0.01, "{:?}", velocity);
+    }
+
+    #[test]
+    fn restoring_a_snapshot_drops_the_held_ball() {
+        let mut world = world();
+        add_ball(&mut world, 10.0);
+        let snapshot = crate::snapshot::Snapshot::capture(&world);
+        let center = world.circle_center;
+        grab_ball(&mut world, center);
+        assert!(world.held_ball.is_some());
+        snapshot.restore(&mut world);
+        assert!(world.held_ball.is_none());
+    }
+
+    #[test]
+    fn spawned_balls_do_not_overlap_while_there_is_room() {
+        let mut world = world();
+        let mut grid = SpatialGrid::new(&world.config);
+        for id in 0..25 {
+            let ball = create_random_ball_with_rng(&world.config, &mut world.rng, world.circle_center, id, &grid, &world.balls);
+            for other in &world.balls {
+                let gap = (ball.position - other.position).length() - ball.radius - other.radius;
+                assert!(gap >= 0.0, "ball {} overlaps ball {} by {}", id, other.id, -gap);
+            }
+            grid.insert(world.balls.len(), ball.position);
+            world.balls.push(ball);
+        }
+    }
+
+    #[test]
+    fn full_ring_falls_back_to_the_clearest_candidate() {
+        let mut world = world();
+        // Réseau plus serré qu'un rayon : aucun point de l'anneau n'est libre
+        let center = world.circle_center;
+        for ix in -14..=14 {
+            for iy in -14..=14 {
+                let position = center + Vector2D { x: ix as f32 * 20.0, y: iy as f32 * 20.0 };
+                let id = world.next_ball_id;
+                world.balls.push(create_ball_at(&world.config, &mut world.rng, position, Vector2D::default(), id));
+                world.next_ball_id += 1;
+            }
+        }
+        let grid = SpatialGrid::build(&world.config, &world.balls);
+        let config = &world.config;
+        let reach = config.ball_radius + config.grid_cell_size * 0.5;
+
+        // Mêmes tirages que `find_spawn_position`, rejoués sur une copie du générateur
+        let mut replay = world.rng;
+        let max_dist = config.circle_radius - config.ball_radius - 10.0;
+        let best_clearance = (0..SPAWN_ATTEMPTS)
+            .map(|_| {
+                let angle = replay.gen_range(0.0..TWO_PI);
+                let dist = max_dist * replay.gen_range(0.0f32..=1.0).sqrt();
+                grid.clearance(&world.balls, center + Vector2D { x: angle.cos() * dist, y: angle.sin() * dist }, reach)
+            })
+            .fold(f32::MIN, f32::max);
+        assert!(best_clearance < config.ball_radius);
+
+        let position = find_spawn_position(config, &mut world.rng, center, &grid, &world.balls);
+        assert_eq!(grid.clearance(&world.balls, position, reach), best_clearance);
+        assert!((position - center).length() <= max_dist + 0.01);
+    }
+
+    #[test]
+    fn merging_keeps_the_mass() {
+        let mut world = world();
+        for radius in [3.0, 4.0, 15.0] {
+            add_ball(&mut world, radius);
+        }
+        assert!(merge_smallest(&mut world, None));
+        let radii: Vec<f32> = world.balls.iter().map(|ball| ball.radius).collect();
+        assert_eq!(radii, [5.0, 15.0]);
+    }
+
+    #[test]
+    fn merge_larger_than_half_a_cell_is_refused() {
+        let mut world = world();
+        for _ in 0..3 {
+            add_ball(&mut world, 15.0);
+        }
+        // 15 et 15 donneraient 21.2, au-delà de la demi-cellule (20)
+        assert!(!merge_smallest(&mut world, None));
+        assert_eq!(world.balls.len(), 3);
+        assert!(world.balls.iter().all(|ball| ball.radius == 15.0));
+
+        // Les apparitions en trop sont alors perdues
+        apply_spawn_requests(&mut world, vec![SpawnRequest { position: None, velocity: None }], None);
+        assert_eq!(world.balls.len(), 3);
+        assert!(world.pending_spawns.is_empty());
+    }
+
+    #[test]
+    fn dropped_balls_follow_the_overflow_policy() {
+        let config = Config { max_balls: 2, overflow_policy: OverflowPolicy::EvictOldest, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        let center = world.circle_center;
+        for _ in 0..3 {
+            drop_ball(&mut world, center);
+        }
+        let ids: Vec<u64> = world.balls.iter().map(|ball| ball.id).collect();
+        assert_eq!(ids, [2, 3]);
+        assert!(world.balls.iter().all(|ball| ball.velocity == Vector2D::default()));
+
+        // Hors de l'anneau, rien n'apparaît
+        let outside = center + Vector2D { x: world.config.circle_radius, y: 0.0 };
+        drop_ball(&mut world, outside);
+        assert_eq!(world.next_ball_id, 4);
+    }
+
+    #[test]
+    fn emitters_follow_the_overflow_policy() {
+        let config = Config { max_balls: 2, overflow_policy: OverflowPolicy::Queue, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        world.balls.clear();
+        let position = world.circle_center;
+        world.emitters.push(Emitter::new(position, 0.0, 0.1, 1.0, 2.0, TICKS_PER_SECOND * 5.0));
+        update_world(&mut world);
+        assert_eq!(world.balls.len(), 2);
+        assert_eq!(world.pending_spawns.len(), 3);
+    }
+
+    #[test]
+    fn emissions_per_tick_are_capped() {
+        let config = Config { max_balls: 100, overflow_policy: OverflowPolicy::Drop, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        world.balls.clear();
+        let position = world.circle_center;
+        world.emitters.push(Emitter::new(position, 0.0, 0.1, 1.0, 2.0, 1e30));
+        update_world(&mut world);
+        assert_eq!(world.balls.len(), MAX_EMITS_PER_TICK as usize);
+        assert!(world.emitters[0].accumulator < 1.0);
     }
 }
@@ -0,0 +1,224 @@
+use sdl2::GameControllerSubsystem;
+use sdl2::controller::{Axis, Button, GameController};
+use crate::game::{World, show_toast};
+use crate::history::MAX_STEPS_PER_FRAME;
+use crate::keymap::Action;
+use crate::params::{find_param, set_param};
+
+// --- Manettes ---
+
+const DEAD_ZONE: f32 = 0.25;        // Fraction de course ignorée au repos
+const TILT_THRESHOLD: f32 = 0.5;    // Course du stick gauche avant de changer la direction de la gravité
+const SPIN_STEPS_PER_TICK: f32 = 0.5;   // Pas de vitesse de l'anneau par pas de simulation, stick droit au bout
+const BOUNCE_STEPS_PER_TICK: f32 = 0.1; // Pas de rebond par pas de simulation, gâchette au fond
+
+/// Manettes branchées ; les branchements en cours de route arrivent comme des événements
+pub struct Gamepads {
+    subsystem: GameControllerSubsystem,
+    controllers: Vec<GameController>,
+    rest_angle: Option<f32>, // Direction de la gravité avant que le stick gauche ne l'incline
+    last_tick: Option<u64>,  // Pas de simulation lors de la lecture précédente
+}
+
+impl Gamepads {
+    /// SDL envoie un `ControllerDeviceAdded` pour chaque manette déjà branchée au démarrage
+    pub fn new(subsystem: GameControllerSubsystem) -> Self {
+        Self { subsystem, controllers: Vec::new(), rest_angle: None, last_tick: None }
+    }
+
+    /// `index` est l'index du périphérique, pas l'identifiant d'instance
+    pub fn added(&mut self, world: &mut World, index: u32) {
+        match self.subsystem.open(index) {
+            Ok(controller) => {
+                show_toast(world, format!("CONTROLLER CONNECTED: {}", controller.name()));
+                self.controllers.push(controller);
+            },
+            Err(e) => show_toast(world, format!("CONTROLLER ERROR: {}", e)),
+        }
+    }
+
+    pub fn removed(&mut self, world: &mut World, instance_id: u32) {
+        let count = self.controllers.len();
+        self.controllers.retain(|controller| controller.instance_id() != instance_id);
+        if self.controllers.len() != count {
+            show_toast(world, "CONTROLLER DISCONNECTED".to_string());
+        }
+    }
+
+    /// Commandes analogiques, lues à chaque image : les sticks et les gâchettes
+    /// agissent tant qu'ils sont tenus, pas seulement quand ils bougent. Les réglages progressifs
+    /// avancent avec le temps simulé depuis la lecture précédente, pas avec le nombre d'images.
+    pub fn update(&mut self, world: &mut World) {
+        // Un retour en arrière ou un chargement fait reculer `tick` : rien ne s'est écoulé
+        let elapsed = self.last_tick.map_or(0, |last| world.tick.saturating_sub(last)).min(MAX_STEPS_PER_FRAME as u64);
+        self.last_tick = Some(world.tick);
+        let ticks = elapsed as f32;
+
+        // Stick gauche : direction de la gravité verticale, qui revient en place quand il est relâché
+        let tilt = self
+            .controllers
+            .iter()
+            .map(|controller| (axis(controller, Axis::LeftX), axis(controller, Axis::LeftY)))
+            .find(|(x, y)| x.hypot(*y) >= TILT_THRESHOLD);
+        match tilt {
+            Some((x, y)) => {
+                self.rest_angle.get_or_insert(world.config.gravity_angle);
+                change(world, "gravity_angle", x.atan2(y));
+            },
+            None => {
+                if let Some(angle) = self.rest_angle.take() {
+                    change(world, "gravity_angle", angle);
+                }
+            },
+        }
+
+        for controller in &self.controllers {
+            // Stick droit : accélère ou freine la rotation de l'anneau
+            nudge(world, "circle_rotation_speed", axis(controller, Axis::RightX) * SPIN_STEPS_PER_TICK * ticks);
+
+            // Gâchettes : droite pour plus de rebond, gauche pour moins
+            let triggers = axis(controller, Axis::TriggerRight) - axis(controller, Axis::TriggerLeft);
+            nudge(world, "bounciness", triggers * BOUNCE_STEPS_PER_TICK * ticks);
+        }
+    }
+}
+
+fn axis(controller: &GameController, axis: Axis) -> f32 {
+    dead_zone(controller.axis(axis))
+}
+
+/// Position d'un axe entre -1 et 1, nulle dans la zone morte
+fn dead_zone(raw: i16) -> f32 {
+    let value = raw as f32 / i16::MAX as f32;
+    if value.abs() < DEAD_ZONE { 0.0 } else { value.clamp(-1.0, 1.0) }
+}
+
+/// Déplace un paramètre d'une fraction de son pas, en s'arrêtant à ses bornes
+fn nudge(world: &mut World, name: &str, steps: f32) {
+    if steps == 0.0 {
+        return;
+    }
+    if let Some(param) = find_param(name) {
+        let value = param.clamp((param.get)(&world.config) + steps * param.step);
+        change(world, name, value);
+    }
+}
+
+/// La configuration n'est revalidée et réappliquée que si la valeur change
+fn change(world: &mut World, name: &str, value: f32) {
+    if let Some(param) = find_param(name)
+        && (param.get)(&world.config) != value
+    {
+        let _ = set_param(world, name, value);
+    }
+}
+
+/// Boutons de la manette, rattachés aux mêmes actions que le clavier
+pub fn button_action(button: Button) -> Option<Action> {
+    match button {
+        Button::A => Some(Action::ToggleGravity),
+        Button::X => Some(Action::FewerBalls),
+        Button::Y => Some(Action::MoreBalls),
+        Button::B => Some(Action::NextSpawnPolicy),
+        Button::Start => Some(Action::Pause),
+        Button::Back => Some(Action::Help),
+        Button::DPadUp => Some(Action::PanelUp),
+        Button::DPadDown => Some(Action::PanelDown),
+        Button::DPadLeft => Some(Action::PanelDecrease),
+        Button::DPadRight => Some(Action::PanelIncrease),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sdl2::sys;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+
+    fn world() -> World {
+        initialize_world(Config::default(), SimRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn buttons_map_to_keyboard_actions() {
+        assert_eq!(button_action(Button::A), Some(Action::ToggleGravity));
+        assert_eq!(button_action(Button::Y), Some(Action::MoreBalls));
+        assert_eq!(button_action(Button::Start), Some(Action::Pause));
+        assert_eq!(button_action(Button::DPadRight), Some(Action::PanelIncrease));
+        assert_eq!(button_action(Button::Guide), None);
+    }
+
+    #[test]
+    fn dead_zone_ignores_small_movements() {
+        assert_eq!(dead_zone(0), 0.0);
+        assert_eq!(dead_zone((i16::MAX as f32 * DEAD_ZONE) as i16 - 1), 0.0);
+        assert_eq!(dead_zone(-(i16::MAX as f32 * DEAD_ZONE) as i16 + 1), 0.0);
+        assert_eq!(dead_zone(i16::MAX / 2), (i16::MAX / 2) as f32 / i16::MAX as f32);
+        assert_eq!(dead_zone(i16::MAX), 1.0);
+        assert_eq!(dead_zone(i16::MIN), -1.0);
+    }
+
+    #[test]
+    fn nudge_stops_at_the_bounds() {
+        let mut world = world();
+        let bounciness = world.config.bounciness;
+        nudge(&mut world, "bounciness", 2.0);
+        assert!((world.config.bounciness - (bounciness + 0.1)).abs() < 1e-6);
+        nudge(&mut world, "bounciness", 1000.0);
+        assert_eq!(world.config.bounciness, 1.2);
+        nudge(&mut world, "bounciness", -1000.0);
+        assert_eq!(world.config.bounciness, 0.1);
+    }
+
+    /// Manette virtuelle de SDL : aucun matériel nécessaire
+    #[test]
+    fn virtual_controller_steers_the_simulation() {
+        let sdl = sdl2::init().unwrap();
+        let subsystem = sdl.game_controller().unwrap();
+        let axes = sys::SDL_GameControllerAxis::SDL_CONTROLLER_AXIS_MAX as i32;
+        let buttons = sys::SDL_GameControllerButton::SDL_CONTROLLER_BUTTON_MAX as i32;
+        let index = unsafe { sys::SDL_JoystickAttachVirtual(sys::SDL_JoystickType::SDL_JOYSTICK_TYPE_GAMECONTROLLER, axes, buttons, 0) };
+        assert!(index >= 0, "{}", sdl2::get_error());
+        let joystick = unsafe { sys::SDL_JoystickOpen(index) };
+        let set_axis = |axis: Axis, value: i16| unsafe {
+            sys::SDL_JoystickSetVirtualAxis(joystick, axis as i32, value);
+            sys::SDL_JoystickUpdate();
+        };
+
+        let mut world = world();
+        let mut gamepads = Gamepads::new(subsystem);
+        gamepads.added(&mut world, index as u32);
+        assert_eq!(gamepads.controllers.len(), 1);
+
+        // Stick gauche à droite : la gravité tourne d'un quart de tour, puis revient
+        set_axis(Axis::LeftX, i16::MAX);
+        gamepads.update(&mut world);
+        assert!((world.config.gravity_angle - std::f32::consts::FRAC_PI_2).abs() < 1e-3);
+        set_axis(Axis::LeftX, 0);
+        gamepads.update(&mut world);
+        assert_eq!(world.config.gravity_angle, 0.0);
+
+        // Gâchette droite enfoncée : plus de rebond à chaque pas de simulation, rien sans pas
+        let bounciness = world.config.bounciness;
+        set_axis(Axis::TriggerRight, i16::MAX);
+        gamepads.update(&mut world);
+        assert_eq!(world.config.bounciness, bounciness);
+        let raised = bounciness + 2.0 * BOUNCE_STEPS_PER_TICK * find_param("bounciness").unwrap().step;
+        world.tick += 2;
+        gamepads.update(&mut world);
+        assert!((world.config.bounciness - raised).abs() < 1e-6);
+        world.tick -= 1;
+        gamepads.update(&mut world);
+        assert!((world.config.bounciness - raised).abs() < 1e-6);
+
+        let instance_id = gamepads.controllers[0].instance_id();
+        gamepads.removed(&mut world, instance_id);
+        assert!(gamepads.controllers.is_empty());
+        unsafe {
+            sys::SDL_JoystickClose(joystick);
+            sys::SDL_JoystickDetachVirtual(index);
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use crate::game::Vector2D;
+
+// --- Gestes tactiles ---
+
+const TAP_SLOP: f32 = 10.0;     // Déplacement maximal (px) d'un doigt pour rester un tapotement
+const TAP_TIME: u32 = 250;      // Durée maximale (ms) d'un tapotement
+const MIN_SPAN: f32 = 1.0;      // Écart minimal (px) entre deux doigts pour mesurer rotation et pincement
+
+/// Événement d'un doigt, en pixels de la fenêtre ; `time` en millisecondes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TouchEvent {
+    Down { finger: i64, position: Vector2D, time: u32 },
+    Motion { finger: i64, position: Vector2D, time: u32 },
+    Up { finger: i64, position: Vector2D, time: u32 },
+}
+
+/// Geste reconnu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    Tap(Vector2D),
+    /// Un doigt a dépassé la tolérance du tapotement ; la position est celle où il s'est posé
+    DragStart(Vector2D),
+    Drag(Vector2D),
+    DragEnd(Vector2D),
+    /// Rotation des deux premiers doigts depuis le dernier geste (radians, sens horaire à l'écran)
+    Rotate(f32),
+    /// Rapport entre l'écart des deux premiers doigts et l'écart précédent
+    Pinch(f32),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Finger {
+    id: i64,
+    start: Vector2D,
+    position: Vector2D,
+    down_at: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    Idle,
+    /// Un seul doigt posé, encore immobile : tapotement ou début de glisser
+    Touching,
+    Dragging,
+    /// Deux doigts ou plus ; `span` et `angle` mesurent le segment entre les deux premiers
+    TwoFingers { span: f32, angle: f32 },
+    /// Le geste à deux doigts est fini, on attend que tous les doigts soient levés
+    Finished,
+}
+
+/// Reconnaît les gestes à partir des événements des doigts, sans dépendre de SDL.
+///
+/// La rotation et le pincement sont calculés à partir des positions des doigts plutôt que
+/// des `MultiGesture` de SDL, qui arrivent en plus des mouvements sans dire quels doigts ont bougé.
+pub struct GestureRecognizer {
+    fingers: Vec<Finger>,
+    mode: Mode,
+}
+
+impl GestureRecognizer {
+    pub fn new() -> Self {
+        Self { fingers: Vec::new(), mode: Mode::Idle }
+    }
+
+    pub fn feed(&mut self, event: TouchEvent) -> Vec<Gesture> {
+        match event {
+            TouchEvent::Down { finger, position, time } => self.down(finger, position, time),
+            TouchEvent::Motion { finger, position, .. } => self.motion(finger, position),
+            TouchEvent::Up { finger, position, time } => self.up(finger, position, time),
+        }
+    }
+
+    fn down(&mut self, id: i64, position: Vector2D, time: u32) -> Vec<Gesture> {
+        self.fingers.retain(|finger| finger.id != id);
+        self.fingers.push(Finger { id, start: position, position, down_at: time });
+
+        let mut gestures = Vec::new();
+        match (self.mode, self.fingers.len()) {
+            (Mode::Idle, 1) => self.mode = Mode::Touching,
+            // Un deuxième doigt interrompt le glisser et passe au geste à deux doigts
+            (Mode::Touching | Mode::Dragging, _) => {
+                if self.mode == Mode::Dragging {
+                    gestures.push(Gesture::DragEnd(self.fingers[0].position));
+                }
+                let (span, angle) = self.segment();
+                self.mode = Mode::TwoFingers { span, angle };
+            },
+            _ => {},
+        }
+        gestures
+    }
+
+    fn motion(&mut self, id: i64, position: Vector2D) -> Vec<Gesture> {
+        let Some(index) = self.fingers.iter().position(|finger| finger.id == id) else {
+            return Vec::new();
+        };
+        self.fingers[index].position = position;
+
+        match self.mode {
+            Mode::Touching if (position - self.fingers[index].start).length() > TAP_SLOP => {
+                self.mode = Mode::Dragging;
+                vec![Gesture::DragStart(self.fingers[index].start), Gesture::Drag(position)]
+            },
+            Mode::Dragging => vec![Gesture::Drag(position)],
+            Mode::TwoFingers { span, angle } if index < 2 => {
+                let (new_span, new_angle) = self.segment();
+                self.mode = Mode::TwoFingers { span: new_span, angle: new_angle };
+                let mut gestures = Vec::new();
+                if span >= MIN_SPAN && new_span >= MIN_SPAN {
+                    let turn = wrap_angle(new_angle - angle);
+                    if turn != 0.0 {
+                        gestures.push(Gesture::Rotate(turn));
+                    }
+                    if new_span != span {
+                        gestures.push(Gesture::Pinch(new_span / span));
+                    }
+                }
+                gestures
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn up(&mut self, id: i64, position: Vector2D, time: u32) -> Vec<Gesture> {
+        let Some(index) = self.fingers.iter().position(|finger| finger.id == id) else {
+            return Vec::new();
+        };
+        let finger = self.fingers.remove(index);
+
+        let gestures = match self.mode {
+            Mode::Touching if time.wrapping_sub(finger.down_at) <= TAP_TIME => vec![Gesture::Tap(finger.start)],
+            Mode::Dragging => vec![Gesture::DragEnd(position)],
+            _ => Vec::new(),
+        };
+        self.mode = if self.fingers.is_empty() { Mode::Idle } else { Mode::Finished };
+        gestures
+    }
+
+    /// Longueur et direction du segment entre les deux premiers doigts
+    fn segment(&self) -> (f32, f32) {
+        match self.fingers.as_slice() {
+            [a, b, ..] => {
+                let axis = b.position - a.position;
+                (axis.length(), axis.y.atan2(axis.x))
+            },
+            _ => (0.0, 0.0),
+        }
+    }
+}
+
+/// Ramène un angle dans ]-π, π]
+fn wrap_angle(angle: f32) -> f32 {
+    use std::f32::consts::PI;
+    let wrapped = angle.rem_euclid(2.0 * PI);
+    if wrapped > PI { wrapped - 2.0 * PI } else { wrapped }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(x: f32, y: f32) -> Vector2D {
+        Vector2D { x, y }
+    }
+
+    fn down(finger: i64, x: f32, y: f32, time: u32) -> TouchEvent {
+        TouchEvent::Down { finger, position: at(x, y), time }
+    }
+
+    fn motion(finger: i64, x: f32, y: f32, time: u32) -> TouchEvent {
+        TouchEvent::Motion { finger, position: at(x, y), time }
+    }
+
+    fn up(finger: i64, x: f32, y: f32, time: u32) -> TouchEvent {
+        TouchEvent::Up { finger, position: at(x, y), time }
+    }
+
+    /// Tous les gestes produits par une suite d'événements
+    fn feed_all(events: &[TouchEvent]) -> Vec<Gesture> {
+        let mut recognizer = GestureRecognizer::new();
+        events.iter().flat_map(|&event| recognizer.feed(event)).collect()
+    }
+
+    #[test]
+    fn quick_touch_is_a_tap() {
+        let gestures = feed_all(&[down(1, 100.0, 100.0, 0), motion(1, 103.0, 101.0, 50), up(1, 103.0, 101.0, 120)]);
+        assert_eq!(gestures, vec![Gesture::Tap(at(100.0, 100.0))]);
+    }
+
+    #[test]
+    fn long_press_is_not_a_tap() {
+        let gestures = feed_all(&[down(1, 100.0, 100.0, 0), up(1, 100.0, 100.0, 600)]);
+        assert!(gestures.is_empty());
+    }
+
+    #[test]
+    fn moving_finger_drags_from_where_it_landed() {
+        let gestures = feed_all(&[
+            down(1, 100.0, 100.0, 0),
+            motion(1, 105.0, 100.0, 10),
+            motion(1, 130.0, 100.0, 20),
+            motion(1, 160.0, 110.0, 30),
+            up(1, 170.0, 110.0, 40),
+        ]);
+        assert_eq!(gestures, vec![
+            Gesture::DragStart(at(100.0, 100.0)),
+            Gesture::Drag(at(130.0, 100.0)),
+            Gesture::Drag(at(160.0, 110.0)),
+            Gesture::DragEnd(at(170.0, 110.0)),
+        ]);
+    }
+
+    #[test]
+    fn second_finger_ends_drag_and_suppresses_tap() {
+        let gestures = feed_all(&[
+            down(1, 100.0, 100.0, 0),
+            motion(1, 150.0, 100.0, 10),
+            down(2, 300.0, 100.0, 20),
+            up(2, 300.0, 100.0, 30),
+            up(1, 150.0, 100.0, 40),
+        ]);
+        assert_eq!(gestures, vec![
+            Gesture::DragStart(at(100.0, 100.0)),
+            Gesture::Drag(at(150.0, 100.0)),
+            Gesture::DragEnd(at(150.0, 100.0)),
+        ]);
+    }
+
+    #[test]
+    fn two_finger_tap_is_ignored() {
+        let gestures = feed_all(&[down(1, 100.0, 100.0, 0), down(2, 200.0, 100.0, 10), up(1, 100.0, 100.0, 60), up(2, 200.0, 100.0, 70)]);
+        assert!(gestures.is_empty());
+    }
+
+    #[test]
+    fn turning_two_fingers_rotates() {
+        // Le second doigt tourne d'un quart de tour autour du premier
+        let gestures = feed_all(&[down(1, 0.0, 0.0, 0), down(2, 100.0, 0.0, 0), motion(2, 0.0, 100.0, 10)]);
+        let [Gesture::Rotate(turn)] = gestures.as_slice() else {
+            panic!("expected a single rotation, got {:?}", gestures);
+        };
+        assert!((turn - std::f32::consts::FRAC_PI_2).abs() < 1e-5);
+    }
+
+    #[test]
+    fn spreading_two_fingers_pinches() {
+        let gestures = feed_all(&[
+            down(1, 100.0, 100.0, 0),
+            down(2, 200.0, 100.0, 0),
+            motion(2, 300.0, 100.0, 10),
+            motion(1, 200.0, 100.0, 20),
+        ]);
+        assert_eq!(gestures, vec![Gesture::Pinch(2.0), Gesture::Pinch(0.5)]);
+    }
+
+    #[test]
+    fn rotation_across_the_half_turn_stays_small() {
+        let gestures = feed_all(&[down(1, 0.0, 0.0, 0), down(2, -100.0, 1.0, 0), motion(2, -100.0, -1.0, 10)]);
+        let [Gesture::Rotate(turn)] = gestures.as_slice() else {
+            panic!("expected a single rotation, got {:?}", gestures);
+        };
+        assert!(turn.abs() < 0.1);
+    }
+
+    #[test]
+    fn third_finger_does_not_change_the_gesture() {
+        let gestures = feed_all(&[down(1, 0.0, 0.0, 0), down(2, 100.0, 0.0, 0), down(3, 50.0, 50.0, 5), motion(3, 80.0, 80.0, 10)]);
+        assert!(gestures.is_empty());
+    }
+}
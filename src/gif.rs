@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use crate::capture::Frame;
+
+// --- Encodeur GIF animé ---
+//
+// Chaque image reçoit sa propre palette de 256 couleurs (coupe médiane), puis ses indices
+// sont compressés en LZW à codes de longueur variable.
+
+const PALETTE_SIZE: usize = 256;
+const MIN_CODE_SIZE: u32 = 8;    // Indices sur 8 bits
+const MAX_CODE_SIZE: u32 = 12;
+const MAX_CODES: u32 = 1 << MAX_CODE_SIZE;
+const BLOCK_SIZE: usize = 255;   // Taille maximale d'un sous-bloc de données
+
+/// Écrit un GIF animé image par image, sans garder les images précédentes en mémoire
+pub struct GifWriter<W: Write> {
+    output: W,
+    width: u16,
+    height: u16,
+}
+
+impl<W: Write> GifWriter<W> {
+    /// En-tête, écran logique sans palette globale et boucle infinie
+    pub fn new(mut output: W, width: u32, height: u32) -> io::Result<Self> {
+        let too_large = || io::Error::new(io::ErrorKind::InvalidInput, "image too large for a gif");
+        let width = u16::try_from(width).map_err(|_| too_large())?;
+        let height = u16::try_from(height).map_err(|_| too_large())?;
+
+        output.write_all(b"GIF89a")?;
+        output.write_all(&width.to_le_bytes())?;
+        output.write_all(&height.to_le_bytes())?;
+        output.write_all(&[0, 0, 0])?; // Pas de palette globale, fond 0, pixels carrés
+        // Extension NETSCAPE2.0 : répétition sans fin
+        output.write_all(&[0x21, 0xFF, 11])?;
+        output.write_all(b"NETSCAPE2.0")?;
+        output.write_all(&[3, 1, 0, 0, 0])?;
+        Ok(Self { output, width, height })
+    }
+
+    /// Ajoute une image affichée pendant `delay` centièmes de seconde
+    pub fn add_frame(&mut self, frame: &Frame, delay: u16) -> io::Result<()> {
+        if frame.width != self.width as u32 || frame.height != self.height as u32 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "frame size differs from the gif size"));
+        }
+        let (palette, indices) = quantize(&frame.rgb);
+
+        // Contrôle graphique : durée d'affichage, pas de transparence
+        self.output.write_all(&[0x21, 0xF9, 4, 0])?;
+        self.output.write_all(&delay.to_le_bytes())?;
+        self.output.write_all(&[0, 0])?;
+
+        // Descripteur d'image avec palette locale de 256 entrées
+        self.output.write_all(&[0x2C, 0, 0, 0, 0])?;
+        self.output.write_all(&self.width.to_le_bytes())?;
+        self.output.write_all(&self.height.to_le_bytes())?;
+        self.output.write_all(&[0x80 | 7])?;
+        for index in 0..PALETTE_SIZE {
+            self.output.write_all(&palette.get(index).copied().unwrap_or([0; 3]))?;
+        }
+
+        self.output.write_all(&[MIN_CODE_SIZE as u8])?;
+        for block in lzw_compress(&indices).chunks(BLOCK_SIZE) {
+            self.output.write_all(&[block.len() as u8])?;
+            self.output.write_all(block)?;
+        }
+        self.output.write_all(&[0])
+    }
+
+    /// Termine le fichier et rend la sortie
+    pub fn finish(mut self) -> io::Result<W> {
+        self.output.write_all(&[0x3B])?;
+        self.output.flush()?;
+        Ok(self.output)
+    }
+}
+
+/// Durée d'affichage de l'image `index` en centièmes de seconde. Les durées sont arrondies
+/// de façon cumulée pour que la cadence moyenne reste exacte (30 ips : 3, 4, 3, 3, 4, 3...).
+pub fn frame_delay(index: u64, fps: u32) -> u16 {
+    let at = |index: u64| (index * 100 + fps as u64 / 2) / fps as u64;
+    (at(index + 1) - at(index)) as u16
+}
+
+// --- Quantification ---
+
+/// Couleur réduite à 5 bits par canal, clé de l'histogramme
+fn color_key(rgb: &[u8]) -> usize {
+    (rgb[0] as usize >> 3) << 10 | (rgb[1] as usize >> 3) << 5 | rgb[2] as usize >> 3
+}
+
+/// Entrée de l'histogramme : somme des vraies couleurs tombées dans la case
+#[derive(Clone, Copy)]
+struct Bin {
+    key: usize,
+    count: u32,
+    sum: [u64; 3],
+}
+
+impl Bin {
+    fn channel(&self, channel: usize) -> usize {
+        self.key >> (10 - channel * 5) & 31
+    }
+}
+
+/// Coupe médiane : la boîte de couleurs la plus étendue est coupée en deux, au milieu
+/// de sa population, jusqu'à avoir 256 boîtes ; chaque boîte donne la moyenne de ses couleurs
+fn quantize(rgb: &[u8]) -> (Vec<[u8; 3]>, Vec<u8>) {
+    let mut histogram = vec![Bin { key: 0, count: 0, sum: [0; 3] }; 1 << 15];
+    for pixel in rgb.chunks_exact(3) {
+        let bin = &mut histogram[color_key(pixel)];
+        bin.count += 1;
+        for (sum, &value) in bin.sum.iter_mut().zip(pixel) {
+            *sum += value as u64;
+        }
+    }
+    for (key, bin) in histogram.iter_mut().enumerate() {
+        bin.key = key;
+    }
+    let bins: Vec<Bin> = histogram.iter().filter(|bin| bin.count > 0).copied().collect();
+
+    let mut boxes = vec![bins];
+    while boxes.len() < PALETTE_SIZE {
+        // Canal le plus étendu de chaque boîte sécable
+        let widest = boxes
+            .iter()
+            .enumerate()
+            .filter(|(_, bins)| bins.len() > 1)
+            .map(|(index, bins)| {
+                let (channel, range) = (0..3)
+                    .map(|channel| {
+                        let values = bins.iter().map(|bin| bin.channel(channel));
+                        (channel, values.clone().max().unwrap_or(0) - values.min().unwrap_or(0))
+                    })
+                    .max_by_key(|&(_, range)| range)
+                    .unwrap_or((0, 0));
+                (index, channel, range)
+            })
+            .max_by_key(|&(_, _, range)| range);
+        let Some((index, channel, _)) = widest else {
+            break; // Moins de 256 couleurs distinctes
+        };
+
+        let mut bins = boxes.swap_remove(index);
+        bins.sort_unstable_by_key(|bin| bin.channel(channel));
+        let half = bins.iter().map(|bin| bin.count as u64).sum::<u64>() / 2;
+        let mut seen = 0;
+        let split = bins
+            .iter()
+            .position(|bin| {
+                seen += bin.count as u64;
+                seen > half
+            })
+            .unwrap_or(0)
+            .clamp(1, bins.len() - 1);
+        let upper = bins.split_off(split);
+        boxes.push(bins);
+        boxes.push(upper);
+    }
+
+    let mut palette = Vec::with_capacity(boxes.len());
+    let mut lookup = vec![0u8; 1 << 15];
+    for (index, bins) in boxes.iter().enumerate() {
+        let count: u64 = bins.iter().map(|bin| bin.count as u64).sum();
+        let mut color = [0u8; 3];
+        for (channel, value) in color.iter_mut().enumerate() {
+            *value = (bins.iter().map(|bin| bin.sum[channel]).sum::<u64>() / count.max(1)) as u8;
+        }
+        palette.push(color);
+        for bin in bins {
+            lookup[bin.key] = index as u8;
+        }
+    }
+    let indices = rgb.chunks_exact(3).map(|pixel| lookup[color_key(pixel)]).collect();
+    (palette, indices)
+}
+
+// --- Compression LZW ---
+
+/// Écrit les codes du poids faible au poids fort, comme GIF les attend
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    count: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u32, bits: u32) {
+        self.buffer |= code << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_compress(indices: &[u8]) -> Vec<u8> {
+    let clear = 1 << MIN_CODE_SIZE;
+    let end = clear + 1;
+    let mut writer = BitWriter { bytes: Vec::new(), buffer: 0, count: 0 };
+    let mut table: HashMap<(u32, u8), u32> = HashMap::new();
+    let mut code_size = MIN_CODE_SIZE + 1;
+    let mut next_code = clear + 2;
+
+    writer.write(clear, code_size);
+    let Some((&first, rest)) = indices.split_first() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+    let mut prefix = first as u32;
+    for &index in rest {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        if next_code < MAX_CODES {
+            table.insert((prefix, index), next_code);
+            next_code += 1;
+            // Le décodeur allonge ses codes dès que le code suivant ne tient plus
+            if next_code > 1 << code_size && code_size < MAX_CODE_SIZE {
+                code_size += 1;
+            }
+        } else {
+            // Table pleine : on repart d'une table vide
+            writer.write(clear, code_size);
+            table.clear();
+            code_size = MIN_CODE_SIZE + 1;
+            next_code = clear + 2;
+        }
+        prefix = index as u32;
+    }
+    writer.write(prefix, code_size);
+    // Le décodeur ajoute encore une entrée en lisant ce dernier code
+    if next_code == 1 << code_size && code_size < MAX_CODE_SIZE {
+        code_size += 1;
+    }
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Décodeur LZW de GIF ; compte les codes d'effacement et la plus grande longueur de code lue
+    fn lzw_decompress(bytes: &[u8]) -> (Vec<u8>, usize, u32) {
+        let clear = 1usize << MIN_CODE_SIZE;
+        let end = clear + 1;
+        let mut position = 0;
+        let mut read = |bits: u32| {
+            let code = (0..bits).fold(0, |code, i| {
+                let bit = (bytes[(position + i as usize) / 8] >> ((position + i as usize) % 8)) & 1;
+                code | (bit as usize) << i
+            });
+            position += bits as usize;
+            code
+        };
+
+        let initial: Vec<Vec<u8>> = (0..=end).map(|index| vec![index as u8]).collect();
+        let mut table = initial.clone();
+        let mut code_size = MIN_CODE_SIZE + 1;
+        let (mut output, mut clears, mut widest) = (Vec::new(), 0, code_size);
+        let mut previous: Option<Vec<u8>> = None;
+        loop {
+            let code = read(code_size);
+            widest = widest.max(code_size);
+            if code == clear {
+                table = initial.clone();
+                code_size = MIN_CODE_SIZE + 1;
+                previous = None;
+                clears += 1;
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            let entry = match (&previous, code < table.len()) {
+                (_, true) => table[code].clone(),
+                (Some(previous), false) if code == table.len() => [previous.as_slice(), &previous[..1]].concat(),
+                _ => panic!("invalid code {}", code),
+            };
+            output.extend_from_slice(&entry);
+            if let Some(previous) = previous
+                && table.len() < MAX_CODES as usize
+            {
+                table.push([previous.as_slice(), &entry[..1]].concat());
+                if table.len() == 1 << code_size && code_size < MAX_CODE_SIZE {
+                    code_size += 1;
+                }
+            }
+            previous = Some(entry);
+        }
+        (output, clears, widest)
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        for indices in [vec![], vec![7], vec![0; 1000], (0..=255).collect()] {
+            let (decoded, clears, _) = lzw_decompress(&lzw_compress(&indices));
+            assert_eq!(decoded, indices);
+            assert_eq!(clears, 1);
+        }
+    }
+
+    #[test]
+    fn lzw_grows_codes_and_resets_the_table() {
+        // Suite peu répétitive : la table se remplit plusieurs fois
+        let mut state = 12345u32;
+        let indices: Vec<u8> = (0..60_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+                (state >> 16) as u8
+            })
+            .collect();
+        let (decoded, clears, widest) = lzw_decompress(&lzw_compress(&indices));
+        assert_eq!(decoded, indices);
+        assert_eq!(widest, MAX_CODE_SIZE);
+        assert!(clears > 2, "{} clear codes", clears);
+    }
+
+    #[test]
+    fn quantize_keeps_at_most_256_colors() {
+        let rgb: Vec<u8> = (0..200 * 200u32).flat_map(|i| [(i % 251) as u8, (i / 200 % 256) as u8, (i * 7 % 256) as u8]).collect();
+        let (palette, indices) = quantize(&rgb);
+        assert_eq!(palette.len(), PALETTE_SIZE);
+        assert_eq!(indices.len(), 200 * 200);
+
+        // Peu de couleurs : elles sont reprises exactement
+        let colors = [[0, 0, 0], [255, 0, 0], [10, 200, 30]];
+        let rgb: Vec<u8> = (0..300).flat_map(|i| colors[i % 3]).collect();
+        let (palette, indices) = quantize(&rgb);
+        assert_eq!(palette.len(), 3);
+        for (pixel, &index) in rgb.chunks_exact(3).zip(&indices) {
+            assert_eq!(palette[index as usize], pixel);
+        }
+    }
+
+    #[test]
+    fn frame_delays_keep_the_average_rate() {
+        assert_eq!((0..6).map(|i| frame_delay(i, 30)).collect::<Vec<_>>(), [3, 4, 3, 3, 4, 3]);
+        assert!((0..20).all(|i| frame_delay(i, 20) == 5));
+        assert!((0..100).all(|i| frame_delay(i, 100) == 1));
+        for fps in [7, 24, 30, 60] {
+            assert_eq!((0..fps as u64).map(|i| frame_delay(i, fps) as u32).sum::<u32>(), 100);
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use std::path::Path;
+use crate::capture::{ImageFormat, capture, offscreen_canvas};
+use crate::game::{World, TICKS_PER_SECOND};
+use crate::history::step_world;
+use crate::rendering::render;
+
+// --- Rendu sans fenêtre ---
+
+pub const DEFAULT_FRAMES: u32 = 60;
+pub const DEFAULT_OUT_DIR: &str = "frames";
+
+/// Options de `--headless`
+pub struct HeadlessOptions {
+    pub frames: u32,
+    pub out_dir: String,
+    pub format: ImageFormat,
+    pub scale: u32, // Facteur de résolution par rapport à la fenêtre
+}
+
+/// Rend les images dans une surface en mémoire et les écrit dans `out_dir`
+/// (`frame_00000.png`, ...). Chaque image avance la simulation d'un pas, quel que soit
+/// le temps de rendu : deux exécutions avec la même graine donnent les mêmes images.
+pub fn run_headless(world: &mut World, options: &HeadlessOptions) -> Result<(), String> {
+    std::fs::create_dir_all(&options.out_dir).map_err(|e| format!("{}: {}", options.out_dir, e))?;
+    let mut canvas = offscreen_canvas(world.config.screen_width * options.scale, world.config.screen_height * options.scale)?;
+    // Cadence nominale, affichée par le HUD et lue par le plafond dynamique
+    world.fps = TICKS_PER_SECOND as u32;
+
+    for index in 0..options.frames {
+        render(&mut canvas, world)?;
+        let name = format!("frame_{:05}.{}", index, options.format.extension());
+        let path = Path::new(&options.out_dir).join(name);
+        capture(&canvas)?.save(&path.to_string_lossy(), options.format)?;
+        step_world(world);
+    }
+    eprintln!("wrote {} frames to {}", options.frames, options.out_dir);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+
+    /// Rend trois images dans un dossier temporaire et retourne leur contenu
+    fn render_frames(name: &str) -> (World, Vec<Vec<u8>>) {
+        let config = Config { screen_width: 64, sim_width: 64, screen_height: 48, circle_radius: 20.0, ball_radius: 4.0, grid_cell_size: 10.0, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(7));
+        let out_dir = std::env::temp_dir().join(format!("suicideballs-{}-{}", std::process::id(), name));
+        let options = HeadlessOptions { frames: 3, out_dir: out_dir.to_string_lossy().to_string(), format: ImageFormat::Bmp, scale: 1 };
+        run_headless(&mut world, &options).unwrap();
+        let frames = (0..3).map(|index| std::fs::read(out_dir.join(format!("frame_{:05}.bmp", index))).unwrap()).collect();
+        std::fs::remove_dir_all(&out_dir).unwrap();
+        (world, frames)
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_frames() {
+        let (world, first) = render_frames("first");
+        let (_, second) = render_frames("second");
+        assert_eq!(world.tick, 3);
+        assert!(first == second);
+        assert!(first[0] != first[2]);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+use crate::game::{World, TICKS_PER_SECOND, MAX_SPEED_EXPONENT, update_world};
+use crate::snapshot::Snapshot;
+
+// --- Historique pour le retour en arrière ---
+
+pub const HISTORY_INTERVAL: u64 = 10;   // Ticks entre deux instantanés
+pub const HISTORY_SECONDS: f32 = 30.0;  // Durée conservée
+const HISTORY_CAPACITY: usize = (HISTORY_SECONDS * TICKS_PER_SECOND) as usize / HISTORY_INTERVAL as usize;
+// Au plus deux fois le nombre de pas du multiplicateur maximal (64x) par image :
+// au-delà, le retard est abandonné plutôt que de laisser l'accumulateur s'emballer
+pub const MAX_STEPS_PER_FRAME: u32 = 2 << MAX_SPEED_EXPONENT;
+pub const FPS_WINDOW: usize = 60; // Images prises en compte pour la moyenne
+
+/// Tampon circulaire d'instantanés compacts (sans les traînées) des dernières secondes
+pub struct History {
+    snapshots: VecDeque<Snapshot>,
+    pub rewinding: bool,    // Touche de retour en arrière maintenue
+    pub scrub: Option<f32>, // Position du curseur de la frise (0 = plus ancien, 1 = présent)
+    scrub_index: Option<usize>,
+    rewind_ticks: u64, // Pas de retour en arrière depuis le dernier instantané restauré
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            snapshots: VecDeque::with_capacity(HISTORY_CAPACITY),
+            rewinding: false,
+            scrub: None,
+            scrub_index: None,
+            rewind_ticks: 0,
+        }
+    }
+
+    /// Durée couverte par l'historique, en secondes
+    pub fn span_seconds(&self) -> f32 {
+        self.snapshots.len() as f32 * HISTORY_INTERVAL as f32 / TICKS_PER_SECOND
+    }
+}
+
+/// Un pas de simulation, enregistré dans l'historique
+pub fn step_world(world: &mut World) {
+    update_world(world);
+    record_history(world);
+}
+
+/// Avance l'horloge de la simulation du temps réel écoulé depuis l'image précédente.
+/// Le multiplicateur change le nombre de pas fixes par image, jamais la durée d'un pas.
+pub fn advance(world: &mut World, accumulator: &mut Duration, frame_time: Duration) {
+    let time_step = Duration::from_secs_f64(1.0 / TICKS_PER_SECOND as f64);
+    if !world.clock.paused || world.history.rewinding {
+        *accumulator += frame_time.mul_f64(world.clock.time_scale());
+    } else {
+        *accumulator = Duration::ZERO;
+    }
+    *accumulator = (*accumulator).min(time_step * MAX_STEPS_PER_FRAME);
+
+    // Pendant le retour en arrière, la simulation recule d'un instantané tous les
+    // `HISTORY_INTERVAL` pas, à la vitesse où elle avance ; pendant le déplacement sur la frise, elle est figée
+    if !world.history.rewinding {
+        world.history.rewind_ticks = 0;
+    }
+    while *accumulator >= time_step {
+        if world.history.rewinding {
+            if world.history.rewind_ticks.is_multiple_of(HISTORY_INTERVAL) {
+                rewind_step(world);
+            }
+            world.history.rewind_ticks += 1;
+        } else if world.history.scrub.is_none() {
+            step_world(world);
+        }
+        *accumulator -= time_step;
+    }
+    while world.clock.pending_steps > 0 {
+        world.clock.pending_steps -= 1;
+        step_world(world);
+    }
+}
+
+/// Images par seconde en temps réel (indépendant du multiplicateur), sur les dernières images
+pub fn measure_fps(frame_times: &mut VecDeque<Duration>, frame_time: Duration) -> u32 {
+    frame_times.push_back(frame_time);
+    if frame_times.len() > FPS_WINDOW {
+        frame_times.pop_front();
+    }
+    let total_duration: Duration = frame_times.iter().sum();
+    if total_duration.is_zero() {
+        0
+    } else {
+        (frame_times.len() as f64 / total_duration.as_secs_f64()) as u32
+    }
+}
+
+/// Enregistre un instantané tous les `HISTORY_INTERVAL` ticks
+pub fn record_history(world: &mut World) {
+    if !world.tick.is_multiple_of(HISTORY_INTERVAL) {
+        return;
+    }
+    let snapshot = Snapshot::capture_compact(world);
+    let history = &mut world.history;
+    if history.snapshots.len() == HISTORY_CAPACITY {
+        history.snapshots.pop_front();
+    }
+    history.snapshots.push_back(snapshot);
+}
+
+/// Recule d'un instantané ; retourne `false` quand l'historique est épuisé
+pub fn rewind_step(world: &mut World) -> bool {
+    // L'instantané du tick courant ne ferait pas reculer : on le saute
+    while let Some(snapshot) = world.history.snapshots.pop_back() {
+        if snapshot.tick < world.tick {
+            restore_keeping_config(world, &snapshot);
+            return true;
+        }
+    }
+    false
+}
+
+/// Place la simulation sur l'instantané correspondant à la position du curseur de la frise
+pub fn scrub_to(world: &mut World, fraction: f32) {
+    let history = &mut world.history;
+    history.scrub = Some(fraction.clamp(0.0, 1.0));
+    if history.snapshots.is_empty() {
+        return;
+    }
+    let index = ((history.snapshots.len() - 1) as f32 * fraction.clamp(0.0, 1.0)).round() as usize;
+    if history.scrub_index != Some(index) {
+        history.scrub_index = Some(index);
+        let snapshots = std::mem::take(&mut history.snapshots);
+        restore_keeping_config(world, &snapshots[index]);
+        world.history.snapshots = snapshots;
+    }
+}
+
+/// Seul l'état de la simulation recule : les réglages changés depuis l'instantané restent en place
+fn restore_keeping_config(world: &mut World, snapshot: &Snapshot) {
+    let config = world.config.clone();
+    let config_errors = std::mem::take(&mut world.config_errors);
+    snapshot.restore(world);
+    if config.spawn_policy != snapshot.config.spawn_policy {
+        world.spawn_policy = config.spawn_policy.build();
+    }
+    world.config = config;
+    world.config_errors = config_errors;
+}
+
+/// Reprend la simulation depuis la position de la frise : le futur abandonné est oublié
+pub fn end_scrub(world: &mut World) {
+    let history = &mut world.history;
+    if let Some(index) = history.scrub_index.take() {
+        history.snapshots.truncate(index + 1);
+    }
+    history.scrub = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+
+    fn world_at_tick(tick: u64) -> World {
+        let mut world = initialize_world(Config::default(), SimRng::seed_from_u64(1));
+        for _ in 0..tick {
+            step_world(&mut world);
+        }
+        world
+    }
+
+    #[test]
+    fn rewind_plays_at_normal_speed() {
+        let mut world = world_at_tick(100);
+        let time_step = Duration::from_secs_f64(1.0 / TICKS_PER_SECOND as f64);
+        let mut accumulator = Duration::ZERO;
+        world.history.rewinding = true;
+        advance(&mut world, &mut accumulator, time_step * 20);
+        // Un instantané tous les dix pas : 20 pas de retour en arrière reculent de 20 ticks
+        assert_eq!(world.tick, 80);
+    }
+
+    #[test]
+    fn rewind_keeps_live_settings() {
+        let mut world = world_at_tick(50);
+        world.config.bounciness = 0.3;
+        assert!(rewind_step(&mut world));
+        assert_eq!(world.tick, 40);
+        assert_eq!(world.config.bounciness, 0.3);
+    }
+
+    #[test]
+    fn history_snapshots_have_no_trails() {
+        let world = world_at_tick(30);
+        assert!(world.balls.iter().any(|ball| !ball.trail.is_empty()));
+        assert!(world.history.snapshots.iter().all(|snapshot| snapshot.balls.iter().all(|ball| ball.trail.is_empty())));
+    }
+}
@@ -1,34 +1,228 @@
 use sdl2::event::Event;
-use sdl2::keyboard::Keycode;
-use crate::game::{World, GravityMode, increase_balls_to_spawn, decrease_balls_to_spawn};
+use sdl2::keyboard::{Keycode, Scancode, TextInputUtil};
+use sdl2::mouse::MouseButton;
+use crate::gamepad::{Gamepads, button_action};
+use crate::gesture::{Gesture, TouchEvent};
+use crate::keymap::Action;
+use crate::snapshot::{quick_save, quick_load};
+use crate::history::{scrub_to, end_scrub};
+use crate::recorder::start_recording;
+use crate::screenshot::request_screenshot;
+use crate::svg::export_svg;
+use crate::params::step_param;
+use crate::console::{toggle_console, console_type, console_backspace, console_history, console_submit};
+use crate::panel::{panel_mouse_down, panel_mouse_motion, panel_mouse_up, panel_move, panel_adjust, panel_activate, panel_cancel};
+use crate::rendering::{timeline_fraction_at, timeline_fraction_along};
+use crate::game::{
+    World, Vector2D, cycle_spawn_policy, cycle_overflow_policy, toggle_pause, request_single_step, speed_up, slow_down, grab_ball, drag_ball, release_ball,
+    drop_ball, spawn_balls, begin_emitter_placement, aim_emitter_placement, finish_emitter_placement, screen_to_sim, zoom_view, rotate_ring,
+};
 
-pub fn process_input(event_pump: &mut sdl2::EventPump, world: &mut World) -> bool {
-    for event in event_pump.poll_iter() {
+/// Identifiant des événements souris que SDL fabrique à partir des doigts (`SDL_TOUCH_MOUSEID`)
+const TOUCH_MOUSE_ID: u32 = u32::MAX;
+
+pub fn process_input(event_pump: &mut sdl2::EventPump, text_input: &TextInputUtil, gamepads: &mut Gamepads, world: &mut World) -> bool {
+    // Collecter les événements d'abord pour pouvoir lire l'état des modificateurs (pour la souris)
+    let events: Vec<Event> = event_pump.poll_iter().collect();
+    let keyboard = event_pump.keyboard_state();
+    let shift_held = keyboard.is_scancode_pressed(Scancode::LShift) || keyboard.is_scancode_pressed(Scancode::RShift);
+
+    for event in events {
+        if world.console.open && console_event(world, &event, text_input) {
+            continue;
+        }
+        if from_touch_in_simulation(world, &event) {
+            continue;
+        }
         match event {
-            Event::Quit {..} |
-            Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+            Event::Quit {..} => {
                 return false;
             },
-            Event::KeyDown { keycode: Some(Keycode::Up), .. } => {
-                world.bounciness = (world.bounciness + 0.05).min(1.2);
+            Event::KeyDown { keycode, scancode, keymod, repeat, .. } => {
+                let action = world.keymap.action_for(keycode, scancode, keymod);
+                if let Some(action) = action
+                    && (!repeat || action.repeats())
+                    && !perform_with_text_input(world, action, text_input)
+                {
+                    return false;
+                }
+            },
+            Event::KeyUp { keycode, scancode, .. } if world.keymap.is_bound(Action::Rewind, keycode, scancode) => {
+                world.history.rewinding = false;
+            },
+            Event::ControllerDeviceAdded { which, .. } => {
+                gamepads.added(world, which);
+            },
+            Event::ControllerDeviceRemoved { which, .. } => {
+                gamepads.removed(world, which);
+            },
+            // Console ouverte : les boutons ne déclenchent rien, comme les touches
+            Event::ControllerButtonDown { button, .. } if !world.console.open => {
+                if let Some(action) = button_action(button)
+                    && !perform_with_text_input(world, action, text_input)
+                {
+                    return false;
+                }
+            },
+            Event::MouseButtonDown { mouse_btn: MouseButton::Left, x, y, .. } => {
+                left_click(world, x, y, shift_held);
+            },
+            Event::MouseButtonDown { mouse_btn: MouseButton::Right, x, y, .. } if in_simulation(world, x) => {
+                begin_emitter_placement(world, screen_to_sim(world, Vector2D { x: x as f32, y: y as f32 }));
             },
-            Event::KeyDown { keycode: Some(Keycode::Down), .. } => {
-                world.bounciness = (world.bounciness - 0.05).max(0.1);
+            Event::MouseMotion { x, .. } if world.history.scrub.is_some() => {
+                scrub_to(world, timeline_fraction_along(world, x));
             },
-            Event::KeyDown { keycode: Some(Keycode::G), .. } => {
-                world.gravity_mode = match world.gravity_mode {
-                    GravityMode::Centripetal => GravityMode::Vertical,
-                    GravityMode::Vertical => GravityMode::Centripetal,
-                };
+            Event::MouseMotion { x, y, .. } => {
+                let point = screen_to_sim(world, Vector2D { x: x as f32, y: y as f32 });
+                drag_ball(world, point);
+                aim_emitter_placement(world, point);
+                panel_mouse_motion(world, x);
             },
-            Event::KeyDown { keycode: Some(Keycode::Plus) | Some(Keycode::Equals) | Some(Keycode::KpPlus), .. } => {
-                increase_balls_to_spawn(world);
+            Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => {
+                if world.history.scrub.is_some() {
+                    end_scrub(world);
+                }
+                release_ball(world);
+                panel_mouse_up(world);
             },
-            Event::KeyDown { keycode: Some(Keycode::Minus) | Some(Keycode::KpMinus), .. } => {
-                decrease_balls_to_spawn(world);
+            Event::MouseButtonUp { mouse_btn: MouseButton::Right, .. } => {
+                finish_emitter_placement(world);
             },
+            // Un doigt posé sur le HUD agit à travers la souris simulée par SDL
+            Event::FingerDown { finger_id, x, y, timestamp, .. } if in_simulation(world, touch_position(world, x, y).x as i32) => {
+                let position = touch_position(world, x, y);
+                touch(world, TouchEvent::Down { finger: finger_id, position, time: timestamp });
+            },
+            Event::FingerMotion { finger_id, x, y, timestamp, .. } => {
+                let position = touch_position(world, x, y);
+                touch(world, TouchEvent::Motion { finger: finger_id, position, time: timestamp });
+            },
+            Event::FingerUp { finger_id, x, y, timestamp, .. } => {
+                let position = touch_position(world, x, y);
+                touch(world, TouchEvent::Up { finger: finger_id, position, time: timestamp });
+            },
+            // Rotation et pincement sont déduits des doigts eux-mêmes, voir `GestureRecognizer`
+            Event::MultiGesture { .. } => {},
             _ => {}
         }
     }
+    gamepads.update(world);
+    true
+}
+
+/// Clic gauche : sur la frise, dans le panneau (ou sa liste ouverte), sinon dans la simulation
+fn left_click(world: &mut World, x: i32, y: i32, shift_held: bool) {
+    if let Some(fraction) = timeline_fraction_at(world, x, y) {
+        scrub_to(world, fraction);
+    } else if world.panel.open.is_some() || !in_simulation(world, x) {
+        panel_mouse_down(world, x, y);
+    } else {
+        let point = screen_to_sim(world, Vector2D { x: x as f32, y: y as f32 });
+        if shift_held {
+            drop_ball(world, point);
+        } else {
+            grab_ball(world, point);
+        }
+    }
+}
+
+/// Une console ouverte par l'action reçoit ensuite le texte tapé
+fn perform_with_text_input(world: &mut World, action: Action, text_input: &TextInputUtil) -> bool {
+    let keep_running = perform(world, action);
+    if world.console.open {
+        text_input.start();
+    }
+    keep_running
+}
+
+/// Exécute une action du clavier ; retourne `false` pour quitter
+pub fn perform(world: &mut World, action: Action) -> bool {
+    match action {
+        // Échap ferme d'abord l'aide ou une liste ouverte du panneau
+        Action::Quit if world.help_open => world.help_open = false,
+        Action::Quit if panel_cancel(world) => {},
+        Action::Quit => return false,
+        Action::PanelUp => panel_move(world, -1),
+        Action::PanelDown => panel_move(world, 1),
+        Action::PanelDecrease => panel_adjust(world, -1),
+        Action::PanelIncrease => panel_adjust(world, 1),
+        Action::PanelActivate => panel_activate(world),
+        Action::ToggleGravity => step_param(world, "gravity_mode", 1),
+        Action::MoreBalls => step_param(world, "balls_to_spawn", 1),
+        Action::FewerBalls => step_param(world, "balls_to_spawn", -1),
+        Action::QuickSave => quick_save(world),
+        Action::QuickLoad => quick_load(world),
+        Action::Pause => toggle_pause(world),
+        Action::Step => request_single_step(world),
+        Action::Slower => slow_down(world),
+        Action::Faster => speed_up(world),
+        Action::Rewind => world.history.rewinding = true,
+        Action::NextSpawnPolicy => cycle_spawn_policy(world),
+        Action::NextOverflowPolicy => cycle_overflow_policy(world),
+        Action::Console => toggle_console(world),
+        Action::Help => world.help_open = !world.help_open,
+        Action::RecordClip => start_recording(world),
+        Action::Screenshot => request_screenshot(world),
+        Action::ExportSvg => export_svg(world),
+    }
+    true
+}
+
+/// Clavier de la console ouverte ; la souris et la fermeture de la fenêtre suivent le traitement normal
+fn console_event(world: &mut World, event: &Event, text_input: &TextInputUtil) -> bool {
+    match event {
+        Event::TextInput { text, .. } => console_type(world, text),
+        Event::KeyDown { keycode, scancode, .. }
+            if *keycode == Some(Keycode::Escape) || world.keymap.is_bound(Action::Console, *keycode, *scancode) =>
+        {
+            toggle_console(world);
+            text_input.stop();
+        },
+        Event::KeyDown { keycode: Some(Keycode::Return) | Some(Keycode::KpEnter), .. } => console_submit(world),
+        Event::KeyDown { keycode: Some(Keycode::Backspace), .. } => console_backspace(world),
+        Event::KeyDown { keycode: Some(Keycode::Up), .. } => console_history(world, -1),
+        Event::KeyDown { keycode: Some(Keycode::Down), .. } => console_history(world, 1),
+        Event::KeyDown { .. } | Event::KeyUp { .. } => {},
+        _ => return false,
+    }
     true
 }
+
+/// SDL double chaque doigt d'un événement souris ; dans la simulation, les gestes le remplacent.
+/// Un clic sert encore à refermer une liste ouverte du panneau.
+fn from_touch_in_simulation(world: &World, event: &Event) -> bool {
+    match *event {
+        Event::MouseButtonDown { which, x, .. } | Event::MouseMotion { which, x, .. } => {
+            which == TOUCH_MOUSE_ID && in_simulation(world, x) && world.panel.open.is_none() && world.history.scrub.is_none()
+        },
+        _ => false,
+    }
+}
+
+/// Les coordonnées des doigts sont relatives à la fenêtre, entre 0 et 1
+fn touch_position(world: &World, x: f32, y: f32) -> Vector2D {
+    Vector2D { x: x * world.config.screen_width as f32, y: y * world.config.screen_height as f32 }
+}
+
+/// Tapoter fait apparaître une balle, glisser lance la balle touchée,
+/// tourner deux doigts fait tourner l'anneau et les pincer agrandit la vue
+fn touch(world: &mut World, event: TouchEvent) {
+    for gesture in world.gestures.feed(event) {
+        match gesture {
+            Gesture::Tap(point) => {
+                spawn_balls(world, 1, Some(screen_to_sim(world, point)));
+            },
+            Gesture::DragStart(point) => grab_ball(world, screen_to_sim(world, point)),
+            Gesture::Drag(point) => drag_ball(world, screen_to_sim(world, point)),
+            Gesture::DragEnd(_) => release_ball(world),
+            Gesture::Rotate(angle) => rotate_ring(world, angle),
+            Gesture::Pinch(scale) => zoom_view(world, scale),
+        }
+    }
+}
+
+/// Les clics dans la zone du HUD n'agissent pas sur la simulation
+fn in_simulation(world: &World, x: i32) -> bool {
+    x >= 0 && (x as u32) < world.config.sim_width
+}
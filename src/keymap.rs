@@ -0,0 +1,403 @@
+use std::fmt;
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use crate::config::ParseError;
+
+// --- Actions du clavier ---
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Quit,
+    PanelUp,
+    PanelDown,
+    PanelDecrease,
+    PanelIncrease,
+    PanelActivate,
+    ToggleGravity,
+    MoreBalls,
+    FewerBalls,
+    QuickSave,
+    QuickLoad,
+    Pause,
+    Step,
+    Slower,
+    Faster,
+    Rewind,
+    NextSpawnPolicy,
+    NextOverflowPolicy,
+    Console,
+    Help,
+    RecordClip,
+    Screenshot,
+    ExportSvg,
+}
+
+pub const ACTIONS: [Action; 23] = [
+    Action::Quit,
+    Action::PanelUp,
+    Action::PanelDown,
+    Action::PanelDecrease,
+    Action::PanelIncrease,
+    Action::PanelActivate,
+    Action::ToggleGravity,
+    Action::MoreBalls,
+    Action::FewerBalls,
+    Action::QuickSave,
+    Action::QuickLoad,
+    Action::Pause,
+    Action::Step,
+    Action::Slower,
+    Action::Faster,
+    Action::Rewind,
+    Action::NextSpawnPolicy,
+    Action::NextOverflowPolicy,
+    Action::Console,
+    Action::Help,
+    Action::RecordClip,
+    Action::Screenshot,
+    Action::ExportSvg,
+];
+
+impl Action {
+    /// Nom utilisé dans le fichier de raccourcis
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::PanelUp => "panel_up",
+            Action::PanelDown => "panel_down",
+            Action::PanelDecrease => "panel_decrease",
+            Action::PanelIncrease => "panel_increase",
+            Action::PanelActivate => "panel_activate",
+            Action::ToggleGravity => "toggle_gravity",
+            Action::MoreBalls => "more_balls",
+            Action::FewerBalls => "fewer_balls",
+            Action::QuickSave => "quick_save",
+            Action::QuickLoad => "quick_load",
+            Action::Pause => "pause",
+            Action::Step => "step",
+            Action::Slower => "slower",
+            Action::Faster => "faster",
+            Action::Rewind => "rewind",
+            Action::NextSpawnPolicy => "next_spawn_policy",
+            Action::NextOverflowPolicy => "next_overflow_policy",
+            Action::Console => "console",
+            Action::Help => "help",
+            Action::RecordClip => "record_clip",
+            Action::Screenshot => "screenshot",
+            Action::ExportSvg => "export_svg",
+        }
+    }
+
+    /// Libellé de l'aide (F1)
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Quit => "quit / close",
+            Action::PanelUp => "previous setting",
+            Action::PanelDown => "next setting",
+            Action::PanelDecrease => "decrease setting",
+            Action::PanelIncrease => "increase setting",
+            Action::PanelActivate => "toggle / open list",
+            Action::ToggleGravity => "gravity mode",
+            Action::MoreBalls => "more balls",
+            Action::FewerBalls => "fewer balls",
+            Action::QuickSave => "quick save",
+            Action::QuickLoad => "quick load",
+            Action::Pause => "pause",
+            Action::Step => "single step",
+            Action::Slower => "slow down",
+            Action::Faster => "speed up",
+            Action::Rewind => "rewind (hold)",
+            Action::NextSpawnPolicy => "spawn policy",
+            Action::NextOverflowPolicy => "overflow policy",
+            Action::Console => "console",
+            Action::Help => "this help",
+            Action::RecordClip => "record 5 s gif clip",
+            Action::Screenshot => "screenshot",
+            Action::ExportSvg => "svg vector image",
+        }
+    }
+
+    /// Les actions à bascule ignorent la répétition automatique d'une touche maintenue
+    pub fn repeats(self) -> bool {
+        !matches!(self, Action::Pause | Action::Console | Action::Help | Action::QuickSave | Action::QuickLoad | Action::RecordClip | Action::Screenshot | Action::ExportSvg)
+    }
+}
+
+// --- Raccourcis ---
+
+/// Touche par son symbole (dépend de la disposition) ou par sa position physique
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Key {
+    Code(Keycode),
+    Scan(Scancode),
+}
+
+/// Modificateurs, sans distinguer gauche et droite
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub gui: bool,
+}
+
+impl Modifiers {
+    pub fn from_mod(keymod: Mod) -> Self {
+        Self {
+            ctrl: keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD),
+            shift: keymod.intersects(Mod::LSHIFTMOD | Mod::RSHIFTMOD),
+            alt: keymod.intersects(Mod::LALTMOD | Mod::RALTMOD),
+            gui: keymod.intersects(Mod::LGUIMOD | Mod::RGUIMOD),
+        }
+    }
+
+    /// Tous les modificateurs demandés sont-ils tenus ?
+    fn held_in(self, held: Modifiers) -> bool {
+        (!self.ctrl || held.ctrl) && (!self.shift || held.shift) && (!self.alt || held.alt) && (!self.gui || held.gui)
+    }
+
+    fn count(self) -> usize {
+        [self.ctrl, self.shift, self.alt, self.gui].iter().filter(|&&held| held).count()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Binding {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl Binding {
+    fn code(keycode: Keycode) -> Self {
+        Self { key: Key::Code(keycode), modifiers: Modifiers::default() }
+    }
+
+    fn scan(scancode: Scancode) -> Self {
+        Self { key: Key::Scan(scancode), modifiers: Modifiers::default() }
+    }
+
+    fn matches_key(&self, keycode: Option<Keycode>, scancode: Option<Scancode>) -> bool {
+        match self.key {
+            Key::Code(code) => keycode == Some(code),
+            Key::Scan(scan) => scancode == Some(scan),
+        }
+    }
+}
+
+/// `ctrl+shift+G`, `scancode:-` : modificateurs, puis un nom de touche SDL.
+/// Le préfixe `scancode:` désigne la position physique plutôt que le symbole.
+impl std::str::FromStr for Binding {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        let mut modifiers = Modifiers::default();
+        let mut rest = text.trim();
+        while let Some((prefix, tail)) = rest.split_once('+') {
+            match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" => modifiers.alt = true,
+                "gui" => modifiers.gui = true,
+                _ => break, // `Keypad +`
+            }
+            rest = tail;
+        }
+        let key = match rest.strip_prefix("scancode:") {
+            Some(name) => Scancode::from_name(name).map(Key::Scan),
+            None => Keycode::from_name(rest).map(Key::Code),
+        };
+        match key {
+            Some(key) => Ok(Binding { key, modifiers }),
+            None => Err(format!("unknown key `{}`", rest)),
+        }
+    }
+}
+
+impl fmt::Display for Binding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names = [(self.modifiers.ctrl, "ctrl+"), (self.modifiers.shift, "shift+"), (self.modifiers.alt, "alt+"), (self.modifiers.gui, "gui+")];
+        for (held, name) in names {
+            if held {
+                write!(f, "{}", name)?;
+            }
+        }
+        match self.key {
+            Key::Code(keycode) => write!(f, "{}", keycode.name()),
+            Key::Scan(scancode) => write!(f, "scancode:{}", scancode.name()),
+        }
+    }
+}
+
+/// Association des touches aux actions
+#[derive(Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Binding)>,
+}
+
+impl Default for Keymap {
+    /// Les touches de ponctuation sont liées par position pour rester à la même place
+    /// sur les claviers AZERTY, QWERTZ...
+    fn default() -> Self {
+        let bindings = vec![
+            (Action::Quit, Binding::code(Keycode::Escape)),
+            (Action::PanelUp, Binding::code(Keycode::Up)),
+            (Action::PanelDown, Binding::code(Keycode::Down)),
+            (Action::PanelDecrease, Binding::code(Keycode::Left)),
+            (Action::PanelIncrease, Binding::code(Keycode::Right)),
+            (Action::PanelActivate, Binding::code(Keycode::Return)),
+            (Action::PanelActivate, Binding::code(Keycode::KpEnter)),
+            (Action::ToggleGravity, Binding::code(Keycode::G)),
+            (Action::MoreBalls, Binding::scan(Scancode::Equals)),
+            (Action::MoreBalls, Binding::code(Keycode::KpPlus)),
+            (Action::FewerBalls, Binding::scan(Scancode::Minus)),
+            (Action::FewerBalls, Binding::code(Keycode::KpMinus)),
+            (Action::QuickSave, Binding::code(Keycode::F5)),
+            (Action::QuickLoad, Binding::code(Keycode::F9)),
+            (Action::Pause, Binding::code(Keycode::Space)),
+            (Action::Step, Binding::scan(Scancode::Period)),
+            (Action::Slower, Binding::scan(Scancode::LeftBracket)),
+            (Action::Faster, Binding::scan(Scancode::RightBracket)),
+            (Action::Rewind, Binding::code(Keycode::Backspace)),
+            (Action::NextSpawnPolicy, Binding::code(Keycode::P)),
+            (Action::NextOverflowPolicy, Binding::code(Keycode::O)),
+            (Action::Console, Binding::scan(Scancode::Grave)),
+            (Action::Help, Binding::code(Keycode::F1)),
+            (Action::RecordClip, Binding::code(Keycode::R)),
+            (Action::Screenshot, Binding::code(Keycode::F12)),
+            (Action::ExportSvg, Binding { key: Key::Code(Keycode::F12), modifiers: Modifiers { shift: true, ..Modifiers::default() } }),
+        ];
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// Action d'une touche pressée ; la liaison qui demande le plus de modificateurs l'emporte
+    pub fn action_for(&self, keycode: Option<Keycode>, scancode: Option<Scancode>, keymod: Mod) -> Option<Action> {
+        let held = Modifiers::from_mod(keymod);
+        self.bindings
+            .iter()
+            .filter(|(_, binding)| binding.matches_key(keycode, scancode) && binding.modifiers.held_in(held))
+            .max_by_key(|(_, binding)| binding.modifiers.count())
+            .map(|&(action, _)| action)
+    }
+
+    /// Une touche relâchée termine l'action maintenue, même si les modificateurs ont changé entre-temps
+    pub fn is_bound(&self, action: Action, keycode: Option<Keycode>, scancode: Option<Scancode>) -> bool {
+        self.bindings.iter().any(|(bound, binding)| *bound == action && binding.matches_key(keycode, scancode))
+    }
+
+    pub fn bindings_for(&self, action: Action) -> impl Iterator<Item = &Binding> {
+        self.bindings.iter().filter(move |(bound, _)| *bound == action).map(|(_, binding)| binding)
+    }
+
+    /// Lit un fichier `action = touche`, une liaison par ligne. Une action présente dans le fichier
+    /// perd ses touches par défaut ; les autres les gardent.
+    pub fn parse(text: &str) -> Result<Keymap, Vec<ParseError>> {
+        let mut keymap = Keymap::default();
+        let mut replaced = Vec::new();
+        let mut errors = Vec::new();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let line = strip_comment(raw_line);
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((name, key)) => match ACTIONS.iter().find(|action| action.name() == name.trim()) {
+                    Some(&action) => key.parse::<Binding>().map(|binding| {
+                        if !replaced.contains(&action) {
+                            replaced.push(action);
+                            keymap.bindings.retain(|(bound, _)| *bound != action);
+                        }
+                        keymap.bindings.push((action, binding));
+                    }),
+                    None => Err(format!("unknown action `{}`", name.trim())),
+                },
+                None => Err(format!("expected `action = key`, found `{}`", line)),
+            };
+            if let Err(message) = result {
+                errors.push(ParseError { line: index + 1, message });
+            }
+        }
+
+        if errors.is_empty() { Ok(keymap) } else { Err(errors) }
+    }
+}
+
+/// Retire le commentaire de fin de ligne ; `\#` désigne la touche `#` elle-même
+fn strip_comment(line: &str) -> String {
+    let mut kept = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.next_if_eq(&'#').is_some() => kept.push('#'),
+            '#' => break,
+            _ => kept.push(c),
+        }
+    }
+    kept
+}
+
+/// Charge un fichier de raccourcis ; les erreurs sont préfixées par le chemin du fichier
+pub fn load_keymap(path: &str) -> Result<Keymap, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Keymap::parse(&text).map_err(|errors| {
+        errors.iter().map(|error| format!("{}:{}: {}", path, error.line, error.message)).collect::<Vec<_>>().join("\n")
+    })
+}
+
+/// Écrit les raccourcis au format du fichier, relisible par `Keymap::parse` : la touche `#`
+/// est écrite `\#` pour ne pas commencer un commentaire
+impl fmt::Display for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (action, binding) in &self.bindings {
+            writeln!(f, "{} = {}", action.name(), binding.to_string().replace('#', "\\#"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binding(key: Key, ctrl: bool, shift: bool) -> Binding {
+        Binding { key, modifiers: Modifiers { ctrl, shift, ..Modifiers::default() } }
+    }
+
+    #[test]
+    fn bindings_read_back_what_they_display() {
+        let extra = [
+            binding(Key::Code(Keycode::Hash), false, false),
+            binding(Key::Code(Keycode::KpPlus), true, false),
+            binding(Key::Scan(Scancode::NonUsHash), false, true),
+            binding(Key::Code(Keycode::Backslash), true, true),
+        ];
+        for binding in Keymap::default().bindings.iter().map(|(_, binding)| *binding).chain(extra) {
+            assert_eq!(binding.to_string().parse::<Binding>(), Ok(binding), "{}", binding);
+        }
+        assert_eq!("ctrl+shift+G".parse::<Binding>(), Ok(binding(Key::Code(Keycode::G), true, true)));
+        assert!("ctrl+Nope".parse::<Binding>().is_err());
+    }
+
+    #[test]
+    fn keymaps_read_back_what_they_display() {
+        let mut keymap = Keymap::default();
+        keymap.bindings.push((Action::Pause, binding(Key::Code(Keycode::Hash), false, false)));
+        keymap.bindings.push((Action::Step, binding(Key::Scan(Scancode::NonUsHash), true, false)));
+        keymap.bindings.push((Action::Help, binding(Key::Code(Keycode::Backslash), false, false)));
+        let text = keymap.to_string();
+        assert!(text.contains("pause = \\#\n"), "{}", text);
+        assert_eq!(Keymap::parse(&text).unwrap().bindings, keymap.bindings);
+    }
+
+    #[test]
+    fn comments_end_at_an_unescaped_hash() {
+        let keymap = Keymap::parse("# touches\npause = \\#   # dièse\nstep = \\\n").unwrap();
+        let pause: Vec<&Binding> = keymap.bindings_for(Action::Pause).collect();
+        assert_eq!(pause, [&binding(Key::Code(Keycode::Hash), false, false)]);
+        let step: Vec<&Binding> = keymap.bindings_for(Action::Step).collect();
+        assert_eq!(step, [&binding(Key::Code(Keycode::Backslash), false, false)]);
+        assert!(Keymap::parse("pause = # rien").is_err());
+    }
+}
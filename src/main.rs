@@ -3,31 +3,133 @@ use std::collections::VecDeque;
 
 mod game;
 mod rendering;
+mod font;
 mod input;
+mod spawn;
+mod config;
+mod cli;
+mod rng;
+mod snapshot;
+mod history;
+mod params;
+mod panel;
+mod console;
+mod keymap;
+mod gamepad;
+mod gesture;
+mod png;
+mod capture;
+mod headless;
+mod video;
+mod gif;
+mod recorder;
+mod screenshot;
+mod svg;
+mod backend;
+mod framebuffer;
+mod terminal;
+mod tty;
 
-use game::{initialize_world, update_world, Config};
+use game::{initialize_world, reload_config, Config};
+use cli::{parse_args, USAGE};
+use config::ConfigWatcher;
+use snapshot::load_from_file;
+use history::{advance, measure_fps};
+use headless::run_headless;
+use video::run_video;
+use terminal::print_frame;
+use tty::run_terminal;
+use rng::SimRng;
+use params::describe_params;
 use rendering::render;
 use input::process_input;
-
+use recorder::record_frame;
+use screenshot::take_screenshot;
+use gamepad::Gamepads;
 
 pub fn main() -> Result<(), String> {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("{}", message);
+            std::process::exit(2);
+        }
+    };
+    if options.help {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+    // Un instantané passé avec --load fournit sa propre configuration
+    let snapshot = match &options.load_path {
+        Some(path) => match load_from_file(path) {
+            Ok(snapshot) => Some(snapshot),
+            Err(message) => {
+                eprintln!("{}", message);
+                std::process::exit(2);
+            }
+        },
+        None => None,
+    };
+    let config = match &snapshot {
+        Some(snapshot) => snapshot.config.clone(),
+        None => options.config,
+    };
+    if options.print_config {
+        print!("{}", config);
+        return Ok(());
+    }
+    if options.list_params {
+        println!("{}", describe_params());
+        return Ok(());
+    }
+    if options.print_keymap {
+        print!("{}", options.keymap);
+        return Ok(());
+    }
+
+    // Une configuration invalide est remplacée par les valeurs par défaut, sans planter
+    let config_errors = config.validate().err().unwrap_or_default();
+    let config = if config_errors.is_empty() { config } else { Config::default() };
+
+    let rng = options.seed.map_or_else(SimRng::from_entropy, SimRng::seed_from_u64);
+    let mut world = initialize_world(config, rng);
+    world.keymap = options.keymap;
+    if let Some(snapshot) = &snapshot {
+        snapshot.restore(&mut world);
+    }
+    world.config_errors = config_errors;
+
+    if let Some(headless) = &options.headless {
+        return run_headless(&mut world, headless);
+    }
+    if let Some(video) = &options.video {
+        return run_video(&mut world, video);
+    }
+    if let Some(columns) = options.print_frame {
+        return print_frame(&world, columns);
+    }
+    let mut config_watcher = options.config_path.map(|path| ConfigWatcher::new(path, options.overrides));
+    if options.terminal {
+        return run_terminal(&mut world, config_watcher);
+    }
+
     let sdl_context = sdl2::init()?;
     let video_subsystem = sdl_context.video()?;
 
-    let config = Config::default();
-
-    let window = video_subsystem.window("Suicide Balls", config.screen_width, config.screen_height)
+    let window = video_subsystem.window("Suicide Balls", world.config.screen_width, world.config.screen_height)
         .position_centered()
         .build()
         .map_err(|e| e.to_string())?;
 
     let mut canvas = window.into_canvas().present_vsync().build().map_err(|e| e.to_string())?;
     let mut event_pump = sdl_context.event_pump()?;
+    // La saisie de texte n'est active que pendant que la console est ouverte
+    let text_input = video_subsystem.text_input();
+    text_input.stop();
+    let mut gamepads = Gamepads::new(sdl_context.game_controller()?);
 
-    let mut world = initialize_world(config);
     let mut frame_times = VecDeque::with_capacity(60);
 
-    let time_step = Duration::from_secs_f64(1.0 / 60.0);
     let mut accumulator = Duration::new(0, 0);
     let mut current_time = Instant::now();
 
@@ -35,31 +137,23 @@ pub fn main() -> Result<(), String> {
         let new_time = Instant::now();
         let frame_time = new_time - current_time;
         current_time = new_time;
-        accumulator += frame_time;
 
-        if !process_input(&mut event_pump, &mut world) {
+        if !process_input(&mut event_pump, &text_input, &mut gamepads, &mut world) {
             break 'running;
         }
 
-        while accumulator >= time_step {
-            update_world(&mut world);
-            accumulator -= time_step;
+        if let Some(watcher) = config_watcher.as_mut() {
+            reload_config(&mut world, watcher);
         }
 
-        // Calculate FPS
-        frame_times.push_back(frame_time);
-        if frame_times.len() > 60 {
-            frame_times.pop_front();
-        }
-        let total_duration: Duration = frame_times.iter().sum();
-        if !total_duration.is_zero() {
-            world.fps = (frame_times.len() as f64 / total_duration.as_secs_f64()) as u32;
-        } else {
-            world.fps = 0;
-        }
+        advance(&mut world, &mut accumulator, frame_time);
+        world.fps = measure_fps(&mut frame_times, frame_time);
 
         render(&mut canvas, &world)?;
+        take_screenshot(&mut world, &canvas);
+        record_frame(&mut world, &canvas)?;
+        canvas.present();
     }
 
     Ok(())
-}
\ No newline at end of file
+}
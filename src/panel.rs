@@ -0,0 +1,325 @@
+use std::mem::discriminant;
+use sdl2::rect::Rect;
+use crate::config::max_ball_radius;
+use crate::game::{Config, World, apply_config};
+use crate::params::{Param, ParamType, find_param, set_param, step_param};
+use crate::rendering::HUD_X_OFFSET;
+use crate::spawn::{OverflowPolicy, SpawnPolicyKind};
+
+// --- Panneau de réglages du HUD ---
+
+const PANEL_TOP: i32 = 120;      // Sous les statistiques
+const ROW_HEIGHT: i32 = 44;      // Étiquette + contrôle + marge
+const CONTROL_WIDTH: u32 = 160;
+const CONTROL_HEIGHT: u32 = 16;
+const OPTION_HEIGHT: u32 = 20;   // Hauteur d'une ligne de liste déroulante
+
+/// Contrôle du panneau
+#[derive(Clone, Copy, PartialEq)]
+pub enum Control {
+    /// Paramètre du registre : curseur pour un nombre, interrupteur pour un choix entre deux valeurs
+    Param(&'static str),
+    SpawnPolicy,
+    OverflowPolicy,
+}
+
+pub const CONTROLS: [Control; 12] = [
+    Control::Param("gravity_mode"),
+    Control::Param("centripetal_gravity"),
+    Control::Param("vertical_gravity"),
+    Control::Param("circle_rotation_speed"),
+    Control::Param("circle_gap_angle"),
+    Control::Param("ball_radius"),
+    Control::Param("max_balls"),
+    Control::Param("friction"),
+    Control::Param("bounciness"),
+    Control::Param("balls_to_spawn"),
+    Control::SpawnPolicy,
+    Control::OverflowPolicy,
+];
+
+/// Forme d'un contrôle à l'écran
+pub enum Widget {
+    Slider { fraction: f32 },
+    Toggle { on: bool },
+    Dropdown,
+}
+
+/// État du panneau : focus clavier, curseur saisi, liste ouverte
+pub struct Panel {
+    pub focus: usize,
+    pub dragging: Option<usize>,
+    pub open: Option<usize>,
+    pub highlight: usize, // Option surlignée dans la liste ouverte
+}
+
+impl Panel {
+    pub fn new() -> Self {
+        // Le focus part sur le rebond, que les flèches réglaient avant le panneau
+        let focus = CONTROLS.iter().position(|&c| c == Control::Param("bounciness")).unwrap_or(0);
+        Self { focus, dragging: None, open: None, highlight: 0 }
+    }
+}
+
+fn param_of(control: Control) -> Option<&'static Param> {
+    match control {
+        Control::Param(name) => find_param(name),
+        _ => None,
+    }
+}
+
+/// Course d'un curseur : l'intervalle du paramètre, restreint par les contraintes entre champs
+/// de `Config::validate` pour que toute la course donne une configuration valide
+fn slider_range(param: &Param, config: &Config) -> (f32, f32) {
+    match param.name {
+        "ball_radius" => (param.min, param.max.min(max_ball_radius(config))),
+        "circle_thickness" => (param.min, param.max.min(config.circle_radius)),
+        _ => (param.min, param.max),
+    }
+}
+
+pub fn label(control: Control) -> &'static str {
+    match control {
+        Control::Param(name) => find_param(name).map_or(name, |param| param.label),
+        Control::SpawnPolicy => "POLICY",
+        Control::OverflowPolicy => "OVERFLOW",
+    }
+}
+
+/// Valeur courante, telle qu'affichée
+pub fn value_text(world: &World, control: Control) -> String {
+    match control {
+        Control::Param(_) => param_of(control)
+            .map(|param| param.display((param.get)(&world.config)))
+            .unwrap_or_default(),
+        Control::SpawnPolicy => world.spawn_policy.name().to_string(),
+        Control::OverflowPolicy => world.config.overflow_policy.name().to_string(),
+    }
+}
+
+pub fn widget(world: &World, control: Control) -> Widget {
+    match param_of(control) {
+        Some(param) => {
+            let value = (param.get)(&world.config);
+            match param.kind {
+                ParamType::Choice(_) => Widget::Toggle { on: value >= 0.5 },
+                _ => {
+                    let (min, max) = slider_range(param, &world.config);
+                    Widget::Slider { fraction: ((value - min) / (max - min)).clamp(0.0, 1.0) }
+                },
+            }
+        },
+        None => Widget::Dropdown,
+    }
+}
+
+fn spawn_policy_options() -> Vec<SpawnPolicyKind> {
+    cycle(SpawnPolicyKind::Multiplier, SpawnPolicyKind::next)
+}
+
+fn overflow_policy_options() -> Vec<OverflowPolicy> {
+    cycle(OverflowPolicy::Drop, OverflowPolicy::next)
+}
+
+/// Parcourt `next` jusqu'à revenir à la première variante
+fn cycle<T: Copy>(first: T, next: fn(T) -> T) -> Vec<T> {
+    let mut options = vec![first];
+    let mut current = next(first);
+    while discriminant(&current) != discriminant(&first) {
+        options.push(current);
+        current = next(current);
+    }
+    options
+}
+
+pub fn dropdown_options(control: Control) -> Vec<&'static str> {
+    match control {
+        Control::SpawnPolicy => spawn_policy_options().into_iter().map(|kind| kind.build().name()).collect(),
+        Control::OverflowPolicy => overflow_policy_options().into_iter().map(OverflowPolicy::name).collect(),
+        Control::Param(_) => Vec::new(),
+    }
+}
+
+fn selected_option(world: &World, control: Control) -> usize {
+    let current = match control {
+        Control::SpawnPolicy => spawn_policy_options().iter().position(|kind| discriminant(kind) == discriminant(&world.config.spawn_policy)),
+        Control::OverflowPolicy => overflow_policy_options().iter().position(|policy| discriminant(policy) == discriminant(&world.config.overflow_policy)),
+        Control::Param(_) => None,
+    };
+    current.unwrap_or(0)
+}
+
+/// Choisit une option ; garder la variante en place conserve ses paramètres (`fixed 20`, ...)
+fn select_option(world: &mut World, control: Control, index: usize) {
+    if index == selected_option(world, control) {
+        return;
+    }
+    let mut config = world.config.clone();
+    match control {
+        Control::SpawnPolicy => config.spawn_policy = spawn_policy_options()[index],
+        Control::OverflowPolicy => config.overflow_policy = overflow_policy_options()[index],
+        Control::Param(_) => return,
+    }
+    apply_config(world, config);
+}
+
+// --- Géométrie ---
+
+/// Position de l'étiquette d'une ligne
+pub fn row_y(index: usize) -> i32 {
+    PANEL_TOP + index as i32 * ROW_HEIGHT
+}
+
+pub fn control_rect(index: usize) -> Rect {
+    Rect::new(HUD_X_OFFSET + 20, row_y(index) + 18, CONTROL_WIDTH, CONTROL_HEIGHT)
+}
+
+/// Ligne `option` de la liste déroulante ouverte sous le contrôle `index`
+pub fn option_rect(index: usize, option: usize) -> Rect {
+    let control = control_rect(index);
+    Rect::new(control.x(), control.bottom() + (option as u32 * OPTION_HEIGHT) as i32, CONTROL_WIDTH, OPTION_HEIGHT)
+}
+
+/// Ligne entière (étiquette et contrôle), pour les clics
+fn row_at(x: i32, y: i32) -> Option<usize> {
+    (0..CONTROLS.len()).find(|&index| {
+        let control = control_rect(index);
+        x >= control.x() && x < control.right() && y >= row_y(index) && y < control.bottom()
+    })
+}
+
+// --- Souris ---
+
+/// Clic dans le panneau. Une liste ouverte se referme au premier clic, où qu'il soit.
+pub fn panel_mouse_down(world: &mut World, x: i32, y: i32) {
+    if let Some(index) = world.panel.open.take() {
+        let count = dropdown_options(CONTROLS[index]).len();
+        if let Some(option) = (0..count).find(|&option| option_rect(index, option).contains_point((x, y))) {
+            select_option(world, CONTROLS[index], option);
+        }
+        return;
+    }
+
+    let Some(index) = row_at(x, y) else {
+        return;
+    };
+    world.panel.focus = index;
+    match widget(world, CONTROLS[index]) {
+        Widget::Slider { .. } => {
+            world.panel.dragging = Some(index);
+            slide_to(world, index, x);
+        },
+        Widget::Toggle { .. } => toggle(world, index),
+        Widget::Dropdown => open_dropdown(world, index),
+    }
+}
+
+pub fn panel_mouse_motion(world: &mut World, x: i32) {
+    if let Some(index) = world.panel.dragging {
+        slide_to(world, index, x);
+    }
+}
+
+pub fn panel_mouse_up(world: &mut World) {
+    world.panel.dragging = None;
+}
+
+/// Place le curseur sous la souris, arrondi au pas du paramètre
+fn slide_to(world: &mut World, index: usize, x: i32) {
+    let Some(param) = param_of(CONTROLS[index]) else {
+        return;
+    };
+    let rect = control_rect(index);
+    let fraction = ((x - rect.x()) as f32 / rect.width() as f32).clamp(0.0, 1.0);
+    let (min, max) = slider_range(param, &world.config);
+    let raw = min + fraction * (max - min);
+    let value = ((raw / param.step).round() * param.step).clamp(min, max);
+    let value = param.clamp(value);
+    if (param.get)(&world.config) != value {
+        // Une valeur refusée laisse la précédente ; les erreurs sont affichées par-dessus la simulation
+        let _ = set_param(world, param.name, value);
+    }
+}
+
+fn toggle(world: &mut World, index: usize) {
+    if let Control::Param(name) = CONTROLS[index] {
+        step_param(world, name, 1);
+    }
+}
+
+fn open_dropdown(world: &mut World, index: usize) {
+    world.panel.highlight = selected_option(world, CONTROLS[index]);
+    world.panel.open = Some(index);
+}
+
+// --- Clavier ---
+
+/// Haut / bas : change le focus, ou l'option surlignée si une liste est ouverte
+pub fn panel_move(world: &mut World, delta: i32) {
+    let panel = &mut world.panel;
+    match panel.open {
+        Some(index) => {
+            let count = dropdown_options(CONTROLS[index]).len() as i32;
+            panel.highlight = (panel.highlight as i32 + delta).rem_euclid(count) as usize;
+        },
+        None => panel.focus = (panel.focus as i32 + delta).rem_euclid(CONTROLS.len() as i32) as usize,
+    }
+}
+
+/// Gauche / droite : règle le contrôle qui a le focus
+pub fn panel_adjust(world: &mut World, steps: i32) {
+    if world.panel.open.is_some() {
+        return;
+    }
+    let index = world.panel.focus;
+    match CONTROLS[index] {
+        Control::Param(name) => step_param(world, name, steps),
+        control => {
+            let count = dropdown_options(control).len() as i32;
+            let option = (selected_option(world, control) as i32 + steps).rem_euclid(count) as usize;
+            select_option(world, control, option);
+        },
+    }
+}
+
+/// Entrée : bascule un interrupteur, ouvre une liste ou valide l'option surlignée
+pub fn panel_activate(world: &mut World) {
+    if let Some(index) = world.panel.open.take() {
+        let option = world.panel.highlight;
+        select_option(world, CONTROLS[index], option);
+        return;
+    }
+    let index = world.panel.focus;
+    match widget(world, CONTROLS[index]) {
+        Widget::Slider { .. } => {},
+        Widget::Toggle { .. } => toggle(world, index),
+        Widget::Dropdown => open_dropdown(world, index),
+    }
+}
+
+/// Échap : ferme la liste ouverte ; retourne `false` s'il n'y en avait pas
+pub fn panel_cancel(world: &mut World) -> bool {
+    world.panel.open.take().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::initialize_world;
+    use crate::rng::SimRng;
+
+    #[test]
+    fn radius_slider_stops_at_half_a_grid_cell() {
+        let mut world = initialize_world(Config::default(), SimRng::seed_from_u64(1));
+        let index = CONTROLS.iter().position(|&control| control == Control::Param("ball_radius")).unwrap();
+        let rect = control_rect(index);
+
+        slide_to(&mut world, index, rect.right());
+        assert_eq!(world.config.ball_radius, 20.0);
+        assert!(world.config_errors.is_empty());
+        assert!(matches!(widget(&world, CONTROLS[index]), Widget::Slider { fraction } if fraction == 1.0));
+
+        slide_to(&mut world, index, rect.x());
+        assert_eq!(world.config.ball_radius, 1.0);
+    }
+}
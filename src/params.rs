@@ -0,0 +1,338 @@
+use crate::config::ConfigError;
+use crate::game::{Config, GravityMode, World, apply_config, show_toast};
+
+const PI: f32 = std::f32::consts::PI;
+const TWO_PI: f32 = 2.0 * PI;
+const MAX_INTEGER: u64 = 1 << 24; // Plus grand entier qu'un f32 représente sans trou
+const MAX_CIRCLE_THICKNESS: f32 = 100.0; // L'anneau est tracé cercle par cercle, un par pixel d'épaisseur
+pub const MAX_BALLS_TO_SPAWN: f32 = 100.0; // Balles créées par sortie, aussi le taux initial maximal
+
+// --- Registre des paramètres réglables ---
+
+/// Type d'un paramètre : fixe la lecture, l'écriture et l'affichage de sa valeur
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ParamType {
+    Float,
+    Integer,
+    /// Valeur parmi une liste de noms, stockée sous forme d'index
+    Choice(&'static [&'static str]),
+}
+
+/// Paramètre nommé de la configuration. Toutes les valeurs passent par un `f32`
+/// (index pour les choix) pour que clavier, HUD, fichiers et console les traitent de la même façon ;
+/// les entiers sont limités à 2^24 pour y rester exacts.
+pub struct Param {
+    pub name: &'static str,
+    pub label: &'static str, // Nom court affiché dans le HUD
+    pub kind: ParamType,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32, // Pas d'une pression de touche
+    pub description: &'static str,
+    pub live: bool, // Modifiable sans redémarrer
+    pub get: fn(&Config) -> f32,
+    pub set: fn(&mut Config, f32),
+}
+
+const GRAVITY_MODES: &[&str] = &["centripetal", "vertical"];
+
+pub const PARAMS: &[Param] = &[
+    Param {
+        name: "screen_width", label: "WIDTH", kind: ParamType::Integer,
+        min: 1.0, max: f32::MAX, step: 10.0,
+        description: "window width in pixels", live: false,
+        get: |c| c.screen_width as f32, set: |c, v| c.screen_width = v as u32,
+    },
+    Param {
+        name: "sim_width", label: "SIM WIDTH", kind: ParamType::Integer,
+        min: 1.0, max: f32::MAX, step: 10.0,
+        description: "width of the simulation area, the HUD takes the rest", live: false,
+        get: |c| c.sim_width as f32, set: |c, v| c.sim_width = v as u32,
+    },
+    Param {
+        name: "screen_height", label: "HEIGHT", kind: ParamType::Integer,
+        min: 1.0, max: f32::MAX, step: 10.0,
+        description: "window height in pixels", live: false,
+        get: |c| c.screen_height as f32, set: |c, v| c.screen_height = v as u32,
+    },
+    Param {
+        name: "max_balls", label: "MAX BALLS", kind: ParamType::Integer,
+        min: 1.0, max: 5000.0, step: 10.0,
+        description: "number of balls before the overflow policy applies", live: true,
+        get: |c| c.max_balls as f32, set: |c, v| c.max_balls = v as usize,
+    },
+    Param {
+        name: "centripetal_gravity", label: "PULL", kind: ParamType::Float,
+        min: -0.5, max: 0.5, step: 0.005,
+        description: "acceleration towards the ring centre in centripetal mode", live: true,
+        get: |c| c.centripetal_gravity, set: |c, v| c.centripetal_gravity = v,
+    },
+    Param {
+        name: "vertical_gravity", label: "FALL", kind: ParamType::Float,
+        min: -0.5, max: 0.5, step: 0.005,
+        description: "downward acceleration in vertical mode", live: true,
+        get: |c| c.vertical_gravity, set: |c, v| c.vertical_gravity = v,
+    },
+    Param {
+        name: "circle_radius", label: "RING", kind: ParamType::Float,
+        min: 1.0, max: f32::MAX, step: 5.0,
+        description: "inner radius of the ring", live: true,
+        get: |c| c.circle_radius, set: |c, v| c.circle_radius = v,
+    },
+    Param {
+        name: "circle_thickness", label: "THICKNESS", kind: ParamType::Float,
+        min: 0.0, max: MAX_CIRCLE_THICKNESS, step: 1.0,
+        description: "thickness of the ring wall, at most the ring radius", live: true,
+        get: |c| c.circle_thickness, set: |c, v| c.circle_thickness = v,
+    },
+    Param {
+        name: "circle_rotation_speed", label: "SPIN", kind: ParamType::Float,
+        min: -0.1, max: 0.1, step: 0.001,
+        description: "ring rotation in radians per tick", live: true,
+        get: |c| c.circle_rotation_speed, set: |c, v| c.circle_rotation_speed = v,
+    },
+    Param {
+        name: "circle_gap_angle", label: "GAP", kind: ParamType::Float,
+        min: 0.0, max: TWO_PI, step: 0.05,
+        description: "opening of the ring in radians", live: true,
+        get: |c| c.circle_gap_angle, set: |c, v| c.circle_gap_angle = v,
+    },
+    Param {
+        name: "ball_radius", label: "RADIUS", kind: ParamType::Float,
+        min: 1.0, max: 100.0, step: 1.0,
+        description: "radius of newly created balls", live: true,
+        get: |c| c.ball_radius, set: |c, v| c.ball_radius = v,
+    },
+    Param {
+        name: "max_velocity", label: "MAX SPEED", kind: ParamType::Float,
+        min: 0.1, max: f32::MAX, step: 1.0,
+        description: "speed limit per tick, prevents tunnelling", live: true,
+        get: |c| c.max_velocity, set: |c, v| c.max_velocity = v,
+    },
+    Param {
+        name: "grid_cell_size", label: "GRID", kind: ParamType::Float,
+        min: 1.0, max: f32::MAX, step: 5.0,
+        description: "cell size of the collision grid, at least one ball diameter", live: true,
+        get: |c| c.grid_cell_size, set: |c, v| c.grid_cell_size = v,
+    },
+    Param {
+        name: "drag_stiffness", label: "STIFFNESS", kind: ParamType::Float,
+        min: 0.0, max: 1.0, step: 0.01,
+        description: "spring stiffness when dragging a ball with the mouse", live: true,
+        get: |c| c.drag_stiffness, set: |c, v| c.drag_stiffness = v,
+    },
+    Param {
+        name: "drag_damping", label: "DAMPING", kind: ParamType::Float,
+        min: 0.0, max: 1.0, step: 0.01,
+        description: "spring damping when dragging a ball with the mouse", live: true,
+        get: |c| c.drag_damping, set: |c, v| c.drag_damping = v,
+    },
+    Param {
+        name: "bounciness", label: "BOUNCE", kind: ParamType::Float,
+        min: 0.1, max: 1.2, step: 0.05,
+        description: "restitution of wall and ball collisions", live: true,
+        get: |c| c.bounciness, set: |c, v| c.bounciness = v,
+    },
+    Param {
+        name: "friction", label: "FRICTION", kind: ParamType::Float,
+        min: 0.9, max: 1.0, step: 0.001,
+        description: "velocity kept after each tick", live: true,
+        get: |c| c.friction, set: |c, v| c.friction = v,
+    },
+    Param {
+        name: "gravity_mode", label: "GRAVITY", kind: ParamType::Choice(GRAVITY_MODES),
+        min: 0.0, max: 1.0, step: 1.0,
+        description: "centripetal (towards the centre) or vertical", live: true,
+        get: |c| match c.gravity_mode {
+            GravityMode::Centripetal => 0.0,
+            GravityMode::Vertical => 1.0,
+        },
+        set: |c, v| c.gravity_mode = if v < 0.5 { GravityMode::Centripetal } else { GravityMode::Vertical },
+    },
+    Param {
+        name: "gravity_angle", label: "TILT", kind: ParamType::Float,
+        min: -PI, max: PI, step: 0.05,
+        description: "direction of vertical gravity in radians, 0 is straight down", live: true,
+        get: |c| c.gravity_angle, set: |c, v| c.gravity_angle = v,
+    },
+    Param {
+        name: "balls_to_spawn", label: "SPAWN", kind: ParamType::Integer,
+        min: 1.0, max: MAX_BALLS_TO_SPAWN, step: 1.0,
+        description: "balls created for each escaped ball", live: true,
+        get: |c| c.balls_to_spawn as f32, set: |c, v| c.balls_to_spawn = v as u32,
+    },
+];
+
+pub fn find_param(name: &str) -> Option<&'static Param> {
+    PARAMS.iter().find(|param| param.name == name)
+}
+
+impl Param {
+    /// Lit une valeur écrite dans un fichier, sur la ligne de commande ou dans la console
+    pub fn parse(&self, text: &str) -> Result<f32, String> {
+        let invalid = || format!("invalid value `{}` for `{}`", text, self.name);
+        match self.kind {
+            ParamType::Float => text.parse().map_err(|_| invalid()),
+            ParamType::Integer => match text.parse::<u64>() {
+                Ok(value) if value <= MAX_INTEGER => Ok(value as f32),
+                Ok(_) => Err(format!("{} = {} is too large, at most {}", self.name, text, MAX_INTEGER)),
+                Err(_) => Err(invalid()),
+            },
+            ParamType::Choice(options) => options
+                .iter()
+                .position(|option| option.eq_ignore_ascii_case(text))
+                .map(|index| index as f32)
+                .ok_or_else(|| format!("invalid {} `{}`: expected {}", self.name, text, options.join(" or "))),
+        }
+    }
+
+    /// Valeur au format du fichier de configuration, relisible par `parse`
+    pub fn format(&self, value: f32) -> String {
+        match self.kind {
+            ParamType::Float => value.to_string(),
+            ParamType::Integer => (value as u64).to_string(),
+            ParamType::Choice(options) => options[self.choice_index(value)].to_string(),
+        }
+    }
+
+    /// Valeur pour le HUD : arrondie au pas, en majuscules
+    pub fn display(&self, value: f32) -> String {
+        match self.kind {
+            ParamType::Float => {
+                let decimals = (-self.step.log10()).ceil().max(0.0) as usize;
+                format!("{:.*}", decimals, value)
+            },
+            _ => self.format(value).to_uppercase(),
+        }
+    }
+
+    /// Avance de `steps` pas : les choix bouclent, les nombres restent dans l'intervalle
+    pub fn stepped(&self, value: f32, steps: i32) -> f32 {
+        match self.kind {
+            ParamType::Choice(options) => {
+                (self.choice_index(value) as i32 + steps).rem_euclid(options.len() as i32) as f32
+            },
+            _ => self.clamp(value + steps as f32 * self.step),
+        }
+    }
+
+    pub fn clamp(&self, value: f32) -> f32 {
+        let value = value.clamp(self.min, self.max);
+        match self.kind {
+            ParamType::Float => value,
+            _ => value.round(),
+        }
+    }
+
+    fn choice_index(&self, value: f32) -> usize {
+        match self.kind {
+            ParamType::Choice(options) => (value.round().max(0.0) as usize).min(options.len() - 1),
+            _ => 0,
+        }
+    }
+}
+
+/// Description du registre pour `--list-params`
+pub fn describe_params() -> String {
+    PARAMS
+        .iter()
+        .map(|param| {
+            let range = match param.kind {
+                ParamType::Choice(options) => options.join(" | "),
+                _ if param.max == f32::MAX => format!(">= {}", param.min),
+                _ => format!("{} .. {}", param.min, param.max),
+            };
+            let live = if param.live { "live" } else { "restart" };
+            format!("{:<22} {:<26} {:<8} {}", param.name, range, live, param.description)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// --- Modification à chaud ---
+
+/// Modifie un paramètre de la simulation en cours. Une valeur hors de son intervalle est refusée,
+/// puis la configuration obtenue est validée comme un fichier rechargé.
+pub fn set_param(world: &mut World, name: &str, value: f32) -> Result<(), String> {
+    let param = find_param(name).ok_or_else(|| format!("unknown parameter `{}`", name))?;
+    if !param.live {
+        return Err(format!("{} needs a restart", name));
+    }
+    if !(param.min..=param.max).contains(&value) {
+        return Err(ConfigError::OutOfRange { field: param.name, value, min: param.min, max: param.max }.to_string());
+    }
+    let mut config = world.config.clone();
+    (param.set)(&mut config, param.clamp(value));
+    if apply_config(world, config) {
+        Ok(())
+    } else {
+        Err(format!("{} = {} gives an invalid configuration", name, param.format(value)))
+    }
+}
+
+/// Comme `set_param`, avec une valeur textuelle (console)
+pub fn set_param_text(world: &mut World, name: &str, text: &str) -> Result<(), String> {
+    let param = find_param(name).ok_or_else(|| format!("unknown parameter `{}`", name))?;
+    let value = param.parse(text)?;
+    set_param(world, name, value)
+}
+
+/// Avance un paramètre d'un nombre de pas (touches du clavier), sans sortir de son intervalle
+pub fn step_param(world: &mut World, name: &str, steps: i32) {
+    let Some(param) = find_param(name) else {
+        return;
+    };
+    let value = param.stepped((param.get)(&world.config), steps);
+    if let Err(message) = set_param(world, name, value) {
+        show_toast(world, message.to_uppercase());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::initialize_world;
+    use crate::rng::SimRng;
+
+    fn world() -> World {
+        initialize_world(Config::default(), SimRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn out_of_range_values_are_refused() {
+        let mut world = world();
+        let bounciness = world.config.bounciness;
+        assert_eq!(set_param(&mut world, "bounciness", 5.0), Err("bounciness = 5 is outside 0.1..1.2".to_string()));
+        assert_eq!(world.config.bounciness, bounciness);
+        assert!(set_param(&mut world, "bounciness", f32::NAN).is_err());
+        assert_eq!(set_param(&mut world, "bounciness", 1.2), Ok(()));
+        assert_eq!(world.config.bounciness, 1.2);
+    }
+
+    #[test]
+    fn ring_thickness_is_bounded() {
+        let mut world = world();
+        assert!(set_param(&mut world, "circle_thickness", 1e9).is_err());
+        assert_eq!(world.config.circle_thickness, 20.0);
+        step_param(&mut world, "circle_thickness", 1000);
+        assert_eq!(world.config.circle_thickness, MAX_CIRCLE_THICKNESS);
+    }
+
+    #[test]
+    fn steps_stop_at_the_bounds() {
+        let mut world = world();
+        step_param(&mut world, "bounciness", 1000);
+        assert_eq!(world.config.bounciness, 1.2);
+        step_param(&mut world, "balls_to_spawn", -1000);
+        assert_eq!(world.config.balls_to_spawn, 1);
+    }
+
+    #[test]
+    fn integers_stay_exact() {
+        let param = find_param("max_balls").unwrap();
+        assert_eq!(param.parse("16777216"), Ok(16_777_216.0));
+        assert!(param.parse("16777217").is_err());
+        assert!(param.parse("3.5").is_err());
+        assert_eq!(param.format(16_777_216.0), "16777216");
+    }
+}
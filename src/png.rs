@@ -0,0 +1,362 @@
+// --- Encodeur PNG ---
+//
+// Juste ce qu'il faut pour écrire des images RGB 8 bits : filtrage des lignes,
+// compression deflate à codes de Huffman fixes et recherche de répétitions par table de hachage.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+const WINDOW_SIZE: usize = 32 * 1024; // Distance maximale d'une répétition deflate
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+const HASH_BITS: u32 = 15;
+
+const LENGTH_BASES: [u16; 29] = [3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DISTANCE_BASES: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+
+/// Encode une image RGB (3 octets par pixel, lignes contiguës)
+pub fn encode_png(width: u32, height: u32, rgb: &[u8]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 2, 0, 0, 0]); // 8 bits, RGB, deflate, filtrage standard, pas d'entrelacement
+
+    let mut png = SIGNATURE.to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib_compress(&filter_rows(width as usize, height as usize, rgb)));
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+pub fn write_png(path: &str, width: u32, height: u32, rgb: &[u8]) -> Result<(), String> {
+    std::fs::write(path, encode_png(width, height, rgb)).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+// --- Filtrage ---
+
+/// Chaque ligne reçoit le filtre qui donne les plus petits écarts (heuristique habituelle)
+fn filter_rows(width: usize, height: usize, rgb: &[u8]) -> Vec<u8> {
+    let stride = width * 3;
+    let mut filtered = Vec::with_capacity(height * (stride + 1));
+    let zero_row = vec![0; stride];
+    let mut candidate = vec![0; stride];
+    let mut best = vec![0; stride];
+
+    for y in 0..height {
+        let row = &rgb[y * stride..(y + 1) * stride];
+        let above = if y == 0 { &zero_row[..] } else { &rgb[(y - 1) * stride..y * stride] };
+        let mut best_filter = 0;
+        let mut best_cost = u64::MAX;
+        for filter in 0..5 {
+            for x in 0..stride {
+                let left = if x >= 3 { row[x - 3] } else { 0 };
+                let upper_left = if x >= 3 { above[x - 3] } else { 0 };
+                let predicted = match filter {
+                    0 => 0,
+                    1 => left,
+                    2 => above[x],
+                    3 => ((left as u16 + above[x] as u16) / 2) as u8,
+                    _ => paeth(left, above[x], upper_left),
+                };
+                candidate[x] = row[x].wrapping_sub(predicted);
+            }
+            // Les octets sont lus comme des écarts signés
+            let cost = candidate.iter().map(|&byte| (byte as i8).unsigned_abs() as u64).sum();
+            if cost < best_cost {
+                best_cost = cost;
+                best_filter = filter;
+                best.copy_from_slice(&candidate);
+            }
+        }
+        filtered.push(best_filter);
+        filtered.extend_from_slice(&best);
+    }
+    filtered
+}
+
+fn paeth(left: u8, above: u8, upper_left: u8) -> u8 {
+    let estimate = left as i16 + above as i16 - upper_left as i16;
+    let (to_left, to_above, to_upper_left) = ((estimate - left as i16).abs(), (estimate - above as i16).abs(), (estimate - upper_left as i16).abs());
+    if to_left <= to_above && to_left <= to_upper_left {
+        left
+    } else if to_above <= to_upper_left {
+        above
+    } else {
+        upper_left
+    }
+}
+
+// --- Compression ---
+
+/// Écrit les bits du poids faible au poids fort, comme deflate les attend
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u64,
+    count: u32,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        Self { bytes: Vec::new(), buffer: 0, count: 0 }
+    }
+
+    fn write_bits(&mut self, value: u32, bits: u32) {
+        self.buffer |= (value as u64) << self.count;
+        self.count += bits;
+        while self.count >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    /// Les codes de Huffman s'écrivent en commençant par leur bit de poids fort
+    fn write_code(&mut self, code: u32, bits: u32) {
+        self.write_bits(code.reverse_bits() >> (32 - bits), bits);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Symbole littéral / longueur dans le code de Huffman fixe
+fn write_symbol(writer: &mut BitWriter, symbol: u32) {
+    match symbol {
+        0..=143 => writer.write_code(0x30 + symbol, 8),
+        144..=255 => writer.write_code(0x190 + symbol - 144, 9),
+        256..=279 => writer.write_code(symbol - 256, 7),
+        _ => writer.write_code(0xC0 + symbol - 280, 8),
+    }
+}
+
+fn write_match(writer: &mut BitWriter, length: usize, distance: usize) {
+    let code = LENGTH_BASES.iter().rposition(|&base| base as usize <= length).unwrap_or(0);
+    write_symbol(writer, 257 + code as u32);
+    writer.write_bits((length - LENGTH_BASES[code] as usize) as u32, LENGTH_EXTRA[code] as u32);
+
+    let code = DISTANCE_BASES.iter().rposition(|&base| base as usize <= distance).unwrap_or(0);
+    writer.write_code(code as u32, 5);
+    writer.write_bits((distance - DISTANCE_BASES[code] as usize) as u32, DISTANCE_EXTRA[code] as u32);
+}
+
+fn hash(data: &[u8]) -> usize {
+    let key = (data[0] as u32) << 16 | (data[1] as u32) << 8 | data[2] as u32;
+    (key.wrapping_mul(2_654_435_761) >> (32 - HASH_BITS)) as usize
+}
+
+/// Flux zlib : un seul bloc deflate à codes fixes, la dernière occurrence de chaque
+/// triplet d'octets servant de candidat pour les répétitions
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::new();
+    writer.write_bits(1, 1); // Dernier bloc
+    writer.write_bits(1, 2); // Codes de Huffman fixes
+
+    let mut last_seen = vec![usize::MAX; 1 << HASH_BITS];
+    let mut position = 0;
+    while position < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if position + MIN_MATCH <= data.len() {
+            let slot = hash(&data[position..]);
+            let candidate = last_seen[slot];
+            last_seen[slot] = position;
+            if candidate != usize::MAX && position - candidate <= WINDOW_SIZE {
+                let limit = MAX_MATCH.min(data.len() - position);
+                length = (0..limit).take_while(|&i| data[candidate + i] == data[position + i]).count();
+                distance = position - candidate;
+            }
+        }
+        if length >= MIN_MATCH {
+            write_match(&mut writer, length, distance);
+            // Indexer les triplets couverts pour les répétitions suivantes
+            for covered in position + 1..(position + length).min(data.len().saturating_sub(MIN_MATCH - 1)) {
+                last_seen[hash(&data[covered..])] = covered;
+            }
+            position += length;
+        } else {
+            write_symbol(&mut writer, data[position] as u32);
+            position += 1;
+        }
+    }
+    write_symbol(&mut writer, 256); // Fin du bloc
+
+    let mut zlib = vec![0x78, 0x01];
+    zlib.extend_from_slice(&writer.finish());
+    zlib.extend_from_slice(&adler32(data).to_be_bytes());
+    zlib
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    b << 16 | a
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { 0xEDB8_8320 ^ (crc >> 1) } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums_match_known_values() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    /// Lecteur de bits pour le décodeur de test, dans l'ordre de deflate
+    struct BitReader<'a> {
+        bytes: &'a [u8],
+        position: usize,
+    }
+
+    impl BitReader<'_> {
+        fn bits(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |value, i| {
+                let bit = (self.bytes[self.position / 8] >> (self.position % 8)) & 1;
+                self.position += 1;
+                value | (bit as u32) << i
+            })
+        }
+
+        /// Code de Huffman : bit de poids fort en premier
+        fn code(&mut self, count: u32) -> u32 {
+            (0..count).fold(0, |code, _| code << 1 | self.bits(1))
+        }
+
+        fn symbol(&mut self) -> u32 {
+            let code = self.code(7);
+            if code <= 0x17 {
+                return 256 + code;
+            }
+            let code = code << 1 | self.bits(1);
+            match code {
+                0x30..=0xBF => code - 0x30,
+                0xC0..=0xC7 => 280 + code - 0xC0,
+                _ => 144 + (code << 1 | self.bits(1)) - 0x190,
+            }
+        }
+    }
+
+    /// Décompresse un flux zlib à codes fixes, le seul que l'encodeur produit
+    fn inflate(zlib: &[u8]) -> Vec<u8> {
+        assert_eq!(zlib[0] & 0x0F, 8, "deflate");
+        assert_eq!((zlib[0] as u16 * 256 + zlib[1] as u16) % 31, 0, "header check");
+        let mut reader = BitReader { bytes: &zlib[2..], position: 0 };
+        assert_eq!(reader.bits(1), 1, "last block");
+        assert_eq!(reader.bits(2), 1, "fixed codes");
+
+        let mut data: Vec<u8> = Vec::new();
+        loop {
+            match reader.symbol() {
+                literal @ 0..=255 => data.push(literal as u8),
+                256 => break,
+                symbol => {
+                    let code = (symbol - 257) as usize;
+                    let length = LENGTH_BASES[code] as usize + reader.bits(LENGTH_EXTRA[code] as u32) as usize;
+                    let code = reader.code(5) as usize;
+                    let distance = DISTANCE_BASES[code] as usize + reader.bits(DISTANCE_EXTRA[code] as u32) as usize;
+                    for _ in 0..length {
+                        data.push(data[data.len() - distance]);
+                    }
+                },
+            }
+        }
+        let checksum = &zlib[zlib.len() - 4..];
+        assert_eq!(checksum, adler32(&data).to_be_bytes());
+        data
+    }
+
+    fn unfilter(width: usize, filtered: &[u8]) -> Vec<u8> {
+        let stride = width * 3;
+        let mut rgb: Vec<u8> = Vec::new();
+        for line in filtered.chunks(stride + 1) {
+            let y = rgb.len() / stride;
+            for x in 0..stride {
+                let left = if x >= 3 { rgb[y * stride + x - 3] } else { 0 };
+                let above = if y > 0 { rgb[(y - 1) * stride + x] } else { 0 };
+                let upper_left = if y > 0 && x >= 3 { rgb[(y - 1) * stride + x - 3] } else { 0 };
+                let predicted = match line[0] {
+                    0 => 0,
+                    1 => left,
+                    2 => above,
+                    3 => ((left as u16 + above as u16) / 2) as u8,
+                    _ => paeth(left, above, upper_left),
+                };
+                rgb.push(line[1 + x].wrapping_add(predicted));
+            }
+        }
+        rgb
+    }
+
+    #[test]
+    fn encoded_image_decodes_to_the_same_pixels() {
+        let (width, height) = (37, 23);
+        // Dégradé, aplats et bruit : tous les filtres et des répétitions de toutes longueurs
+        let rgb: Vec<u8> = (0..width * height * 3)
+            .map(|i| match (i / (width * 3)) % 3 {
+                0 => (i % 256) as u8,
+                1 => 200,
+                _ => (i as u32).wrapping_mul(2_654_435_761).rotate_left(7) as u8,
+            })
+            .collect();
+        let png = encode_png(width as u32, height as u32, &rgb);
+        assert_eq!(png[..8], SIGNATURE);
+
+        let mut chunks = Vec::new();
+        let mut position = 8;
+        while position < png.len() {
+            let length = u32::from_be_bytes(png[position..position + 4].try_into().unwrap()) as usize;
+            let kind = &png[position + 4..position + 8];
+            let data = &png[position + 8..position + 8 + length];
+            let crc = u32::from_be_bytes(png[position + 8 + length..position + 12 + length].try_into().unwrap());
+            assert_eq!(crc, crc32(&png[position + 4..position + 8 + length]));
+            chunks.push((kind.to_vec(), data.to_vec()));
+            position += 12 + length;
+        }
+        let kinds: Vec<&[u8]> = chunks.iter().map(|(kind, _)| kind.as_slice()).collect();
+        assert_eq!(kinds, [b"IHDR".as_slice(), b"IDAT", b"IEND"]);
+
+        let header = &chunks[0].1;
+        assert_eq!(header[..8], [0, 0, 0, 37, 0, 0, 0, 23]);
+        assert_eq!(header[8..], [8, 2, 0, 0, 0]);
+        assert!(chunks[2].1.is_empty());
+
+        let filtered = inflate(&chunks[1].1);
+        assert_eq!(filtered.len(), height * (width * 3 + 1));
+        assert_eq!(unfilter(width, &filtered), rgb);
+    }
+}
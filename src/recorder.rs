@@ -0,0 +1,137 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::sync::mpsc::{Sender, channel};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+use sdl2::rect::Rect;
+use sdl2::render::{Canvas, RenderTarget};
+use crate::capture::{Frame, capture_area, timestamp};
+use crate::game::{World, show_toast};
+use crate::gif::{GifWriter, frame_delay};
+
+// --- Enregistrement d'un clip GIF ---
+
+pub const CLIP_DURATION: Duration = Duration::from_secs(5);
+const CLIP_FPS: u32 = 20; // Au-delà, les GIF deviennent lourds sans être plus fluides
+
+/// Clip en cours : les images capturées partent vers un fil d'encodage,
+/// pour que la quantification ne ralentisse pas l'affichage
+pub struct Recorder {
+    pub path: String,
+    started: Instant,
+    frames: u64,
+    sender: Option<Sender<Frame>>, // `None` une fois la capture terminée
+    encoder: JoinHandle<Result<(), String>>,
+}
+
+impl Recorder {
+    /// Les images sont encore capturées (l'encodage peut se poursuivre après)
+    pub fn capturing(&self) -> bool {
+        self.sender.is_some()
+    }
+}
+
+/// Lance l'enregistrement des prochaines secondes ; pendant la capture, la touche l'arrête plus tôt
+pub fn start_recording(world: &mut World) {
+    if let Some(recorder) = &mut world.recorder {
+        if recorder.capturing() {
+            recorder.sender = None;
+        }
+        return;
+    }
+
+    record_to(world, format!("clip-{}.gif", timestamp()));
+}
+
+/// Ouvre le fichier du clip et lance le fil d'encodage
+fn record_to(world: &mut World, path: String) {
+    let (width, height) = (world.config.sim_width, world.config.screen_height);
+    let (sender, receiver) = channel::<Frame>();
+    let file_path = path.clone();
+    let encoder = std::thread::spawn(move || {
+        let write_error = |e: std::io::Error| format!("{}: {}", file_path, e);
+        let file = File::create(&file_path).map_err(write_error)?;
+        let mut gif = GifWriter::new(BufWriter::new(file), width, height).map_err(write_error)?;
+        for (index, frame) in receiver.iter().enumerate() {
+            gif.add_frame(&frame, frame_delay(index as u64, CLIP_FPS)).map_err(write_error)?;
+        }
+        gif.finish().map(drop).map_err(write_error)
+    });
+    world.recorder = Some(Recorder { path, started: Instant::now(), frames: 0, sender: Some(sender), encoder });
+}
+
+/// Capture la zone de simulation quand une image du clip est due, puis signale la fin de l'encodage.
+/// Appelée après le rendu, avant l'affichage.
+pub fn record_frame<T: RenderTarget>(world: &mut World, canvas: &Canvas<T>) -> Result<(), String> {
+    let Some(recorder) = &mut world.recorder else {
+        return Ok(());
+    };
+
+    let elapsed = recorder.started.elapsed();
+    if elapsed >= CLIP_DURATION {
+        recorder.sender = None;
+    }
+    if let Some(sender) = &recorder.sender
+        && recorder.frames as f64 <= elapsed.as_secs_f64() * CLIP_FPS as f64
+    {
+        let frame = capture_area(canvas, Rect::new(0, 0, world.config.sim_width, world.config.screen_height))?;
+        // Un envoi refusé veut dire que l'encodeur a échoué : l'erreur sort au `join`
+        if sender.send(frame).is_err() {
+            recorder.sender = None;
+        }
+        recorder.frames += 1;
+    }
+
+    if recorder.capturing() || !recorder.encoder.is_finished() {
+        return Ok(());
+    }
+    if let Some(recorder) = world.recorder.take() {
+        let message = match recorder.encoder.join() {
+            Ok(Ok(())) => format!("SAVED {}", recorder.path),
+            Ok(Err(message)) => format!("CLIP FAILED: {}", message),
+            Err(_) => "CLIP FAILED".to_string(),
+        };
+        show_toast(world, message);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::offscreen_canvas;
+    use crate::game::{Config, initialize_world};
+    use crate::rendering::render;
+    use crate::rng::SimRng;
+
+    #[test]
+    fn stopped_clip_is_written_as_a_gif() {
+        let config = Config { screen_width: 64, sim_width: 48, screen_height: 48, circle_radius: 20.0, ball_radius: 4.0, grid_cell_size: 10.0, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        let mut canvas = offscreen_canvas(64, 48).unwrap();
+        render(&mut canvas, &world).unwrap();
+        let path = std::env::temp_dir().join(format!("suicideballs-{}-clip.gif", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+
+        record_to(&mut world, path.clone());
+        record_frame(&mut world, &canvas).unwrap();
+        assert!(world.recorder.as_ref().is_some_and(|recorder| recorder.capturing() && recorder.frames == 1));
+        // La touche d'enregistrement arrête la capture ; l'encodage se termine ensuite
+        start_recording(&mut world);
+        assert!(world.recorder.as_ref().is_some_and(|recorder| !recorder.capturing()));
+        for _ in 0..500 {
+            record_frame(&mut world, &canvas).unwrap();
+            if world.recorder.is_none() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(world.recorder.is_none());
+        assert_eq!(world.toast.as_ref().map(|toast| toast.message.clone()), Some(format!("SAVED {}", path)));
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+        assert_eq!(bytes.last(), Some(&0x3b));
+    }
+}
@@ -1,499 +1,627 @@
 use sdl2::pixels::Color;
-use sdl2::render::Canvas;
-use sdl2::video::Window;
-use crate::game::{World, Vector2D, Ball, TRAIL_LENGTH};
+use sdl2::render::BlendMode;
+use crate::backend::Backend;
+use crate::font::{Align, GLYPH_HEIGHT, text_pixels, text_size};
+use crate::game::{World, Vector2D, Ball, Emitter, held_ball, sim_to_screen, TOAST_DURATION};
+use crate::history::HISTORY_SECONDS;
+use crate::keymap::{Action, ACTIONS};
+use crate::recorder::Recorder;
+use crate::panel::{CONTROLS, Widget, control_rect, dropdown_options, label, option_rect, row_y, value_text, widget};
 
-const HUD_X_OFFSET: i32 = 600;
+pub const HUD_X_OFFSET: i32 = 600;
+pub const FOCUS_COLOR: Color = Color::RGB(255, 220, 120);
+pub const CONSOLE_LINES: usize = 12; // Lignes de sortie visibles dans la console
 const TWO_PI: f32 = 2.0 * std::f32::consts::PI;
 
-pub fn render(canvas: &mut Canvas<Window>, world: &World) -> Result<(), String> {
-    canvas.set_draw_color(Color::RGB(20, 20, 30));
+pub const RING_COLOR: Color = Color::RGB(200, 200, 220);
+pub const BACKGROUND_COLOR: Color = Color::RGB(20, 20, 30);
+pub const HUD_COLOR: Color = Color::RGB(30, 30, 40);
+pub const SEPARATOR_COLOR: Color = Color::RGB(100, 100, 120);
+pub const EMITTER_COLOR: Color = Color::RGB(120, 200, 255);
+pub const HIGHLIGHT_RADIUS: f32 = 2.0; // Reflet tournant des balles
+pub const HUD_TEXT_SCALE: u32 = 2;
+
+/// Passage de la simulation aux pixels de la cible : zoom de la vue, puis facteur de résolution
+#[derive(Clone, Copy)]
+pub struct View<'a> {
+    pub world: &'a World,
+    pub scale: f32,
+}
+
+impl View<'_> {
+    pub fn point(&self, point: Vector2D) -> Vector2D {
+        sim_to_screen(self.world, point) * self.scale
+    }
+
+    /// Longueur de la simulation (rayon, épaisseur) en pixels de la cible
+    pub fn length(&self, length: f32) -> f32 {
+        length * self.world.zoom * self.scale
+    }
+}
+
+pub fn render<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    canvas.set_draw_color(BACKGROUND_COLOR);
     canvas.clear();
 
-    // Draw simulation items
-    draw_arc(
-        canvas,
-        world.circle_center,
-        world.config.circle_radius,
-        world.circle_angle + world.config.circle_gap_angle * 0.5,
-        world.circle_angle - world.config.circle_gap_angle * 0.5 + TWO_PI,
-        world.config.circle_thickness as i32,
-        Color::RGB(200, 200, 220)
-    )?;
+    // Facteur de résolution : 1 dans la fenêtre, plus pour une cible agrandie (export vidéo)
+    let scale = canvas.output_size()?.0 as f32 / world.config.screen_width as f32;
+    let view = View { world, scale };
+    canvas.set_scale(1.0, 1.0)?;
+
+    // Draw simulation items (through the view zoom)
+    let ring = ring_shape(&view);
+    draw_arc(canvas, ring.center, ring.radius, ring.start_angle, ring.end_angle, ring.thickness, RING_COLOR)?;
 
     for ball in &world.balls {
         // Dessiner la traînée avec dégradé de couleur
-        draw_trail(canvas, ball)?;
+        draw_trail(canvas, &view, ball)?;
         
         // Dessiner la balle avec rotation
-        draw_ball_with_rotation(canvas, ball)?;
+        draw_ball_with_rotation(canvas, &view, ball)?;
+    }
+
+    // Émetteurs et émetteur en cours de placement
+    for emitter in &world.emitters {
+        draw_emitter(canvas, &view, emitter)?;
+    }
+    if let Some((origin, aim)) = world.emitter_placement {
+        let (origin, aim) = (view.point(origin), view.point(aim));
+        canvas.set_draw_color(EMITTER_COLOR);
+        canvas.draw_line((origin.x as i32, origin.y as i32), (aim.x as i32, aim.y as i32))?;
+        draw_circle_outline(canvas, origin.x as i32, origin.y as i32, (6.0 * scale) as i32, EMITTER_COLOR)?;
+    }
+
+    // Ressort entre la balle saisie et le curseur
+    if let Some((ball, target)) = held_ball(world) {
+        draw_spring(canvas, view.point(ball.position), view.point(target))?;
+    }
+
+    // Le HUD et les messages sont simplement agrandis : la police est faite de pixels
+    canvas.set_scale(scale, scale)?;
+
+    if !world.config_errors.is_empty() {
+        draw_config_errors(canvas, world)?;
+    }
+    if let Some(message) = visible_toast(world) {
+        draw_toast(canvas, world, message)?;
     }
 
     // Draw HUD background and separator
-    canvas.set_draw_color(Color::RGB(30, 30, 40));
+    canvas.set_draw_color(HUD_COLOR);
     canvas.fill_rect(sdl2::rect::Rect::new(HUD_X_OFFSET, 0, world.config.screen_width - world.config.sim_width, world.config.screen_height))?;
-    canvas.set_draw_color(Color::RGB(100, 100, 120));
+    canvas.set_draw_color(SEPARATOR_COLOR);
     canvas.draw_line((HUD_X_OFFSET, 0), (HUD_X_OFFSET, world.config.screen_height as i32))?;
 
     // Draw HUD text
-    let fps_text = format!("FPS: {}", world.fps);
-    let ball_count_text = format!("BALLS: {}", world.balls.len());
-    let wall_col_text = format!("WALL: {}", world.total_wall_collisions);
-    let ball_col_text = format!("BALL: {}", world.total_ball_collisions);
-    let bounciness_text = format!("BOUNCE: {:.2}", world.bounciness);
-    let gravity_text = format!("GRAVITY: {:?}", world.gravity_mode).to_uppercase();
-    let spawn_text = format!("SPAWN: {}", world.balls_to_spawn);
-
-    draw_text(canvas, &fps_text, HUD_X_OFFSET + 20, 20, 3, Color::WHITE)?;
-    draw_text(canvas, &ball_count_text, HUD_X_OFFSET + 20, 50, 3, Color::WHITE)?;
-    draw_text(canvas, &wall_col_text, HUD_X_OFFSET + 20, 80, 3, Color::WHITE)?;
-    draw_text(canvas, &ball_col_text, HUD_X_OFFSET + 20, 110, 3, Color::WHITE)?;
-    draw_text(canvas, &bounciness_text, HUD_X_OFFSET + 20, 140, 3, Color::WHITE)?;
-    draw_text(canvas, &gravity_text, HUD_X_OFFSET + 20, 170, 3, Color::WHITE)?;
-    draw_text(canvas, &spawn_text, HUD_X_OFFSET + 20, 200, 3, Color::WHITE)?;
-
-    canvas.present();
+    for (i, text) in hud_stats(world).iter().enumerate() {
+        let (x, y) = hud_stat_position(i);
+        draw_text(canvas, text, x, y, HUD_TEXT_SCALE, Color::WHITE)?;
+    }
+
+    // Témoin d'enregistrement, dans le HUD pour rester hors du clip
+    if let Some(recorder) = &world.recorder {
+        let (text, color) = if recorder.capturing() { ("REC", Color::RGB(230, 60, 60)) } else { ("SAVING", FOCUS_COLOR) };
+        draw_aligned_text(canvas, text, world.config.screen_width as i32 - 20, 10, 2, color, Align::Right)?;
+    }
+
+    draw_panel(canvas, world)?;
+    draw_timeline(canvas, world)?;
+
+    if world.help_open {
+        draw_help(canvas, world)?;
+    }
+    if world.console.open {
+        draw_console(canvas, world)?;
+    }
+
     Ok(())
 }
 
-fn draw_text(canvas: &mut Canvas<Window>, text: &str, x: i32, y: i32, scale: u32, color: Color) -> Result<(), String> {
-    canvas.set_draw_color(color);
-    let mut current_x = x;
-    for ch in text.chars() {
-        let char_map = get_char_map(ch.to_ascii_uppercase());
-        for (row_idx, row) in char_map.iter().enumerate() {
-            for (col_idx, &pixel) in row.iter().enumerate() {
-                if pixel == 1 {
-                    let rect = sdl2::rect::Rect::new(
-                        current_x + (col_idx as u32 * scale) as i32,
-                        y + (row_idx as u32 * scale) as i32,
-                        scale,
-                        scale
-                    );
-                    canvas.fill_rect(rect)?;
-                }
-            }
+/// Lignes de statistiques du HUD
+pub fn hud_stats(world: &World) -> [String; 6] {
+    let speed_text = if world.clock.paused {
+        "PAUSED".to_string()
+    } else {
+        format!("SPEED: {}", world.clock.speed_label())
+    };
+    [
+        format!("FPS: {}", world.fps),
+        format!("BALLS: {}", world.balls.len()),
+        format!("WALL: {}", world.total_wall_collisions),
+        format!("BALL: {}", world.total_ball_collisions),
+        format!("PENDING: {}", world.pending_spawns.len()),
+        speed_text,
+    ]
+}
+
+pub fn hud_stat_position(line: usize) -> (i32, i32) {
+    (HUD_X_OFFSET + 20, 10 + line as i32 * 18)
+}
+
+/// Message temporaire encore affiché ; il n'apparaît pas dans un clip en cours de capture
+pub fn visible_toast(world: &World) -> Option<&str> {
+    world.toast.as_ref()
+        .filter(|toast| toast.shown_at.elapsed() < TOAST_DURATION && !world.recorder.as_ref().is_some_and(Recorder::capturing))
+        .map(|toast| toast.message.as_str())
+}
+
+/// Panneau de réglages : étiquette et valeur sur une ligne, contrôle en dessous
+pub fn draw_panel<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    for (index, &control) in CONTROLS.iter().enumerate() {
+        let focused = world.panel.focus == index;
+        let color = if focused { FOCUS_COLOR } else { Color::WHITE };
+        let rect = control_rect(index);
+        let value = value_text(world, control);
+        let name = label(control);
+        draw_text(canvas, name, rect.x(), row_y(index), 2, color)?;
+        // Une valeur trop longue pour tenir à côté de l'étiquette est écrite en petit, sur la même ligne de base
+        let value_scale = if text_size(name, 2).0 + text_size(&value, 2).0 + 6 > rect.width() { 1 } else { 2 };
+        let value_y = row_y(index) + (GLYPH_HEIGHT * (2 - value_scale)) as i32;
+
+        match widget(world, control) {
+            Widget::Slider { fraction } => {
+                draw_aligned_text(canvas, &value, rect.right(), value_y, value_scale, color, Align::Right)?;
+                canvas.set_draw_color(Color::RGB(70, 70, 90));
+                canvas.fill_rect(sdl2::rect::Rect::new(rect.x(), rect.center().y() - 2, rect.width(), 4))?;
+                let knob_x = rect.x() + (fraction * (rect.width() - 8) as f32) as i32;
+                canvas.set_draw_color(Color::RGB(120, 160, 255));
+                canvas.fill_rect(sdl2::rect::Rect::new(rect.x(), rect.center().y() - 2, (knob_x - rect.x()).max(0) as u32, 4))?;
+                canvas.set_draw_color(color);
+                canvas.fill_rect(sdl2::rect::Rect::new(knob_x, rect.y(), 8, rect.height()))?;
+            },
+            Widget::Toggle { on } => {
+                draw_aligned_text(canvas, &value, rect.right(), value_y, value_scale, color, Align::Right)?;
+                canvas.set_draw_color(color);
+                canvas.draw_rect(rect)?;
+                let half = rect.width() / 2;
+                let knob_x = if on { rect.x() + half as i32 } else { rect.x() };
+                canvas.set_draw_color(Color::RGB(120, 160, 255));
+                canvas.fill_rect(sdl2::rect::Rect::new(knob_x + 2, rect.y() + 2, half - 4, rect.height() - 4))?;
+            },
+            Widget::Dropdown => {
+                canvas.set_draw_color(color);
+                canvas.draw_rect(rect)?;
+                draw_text(canvas, &value, rect.x() + 4, rect.y() + 1, 2, color)?;
+            },
+        }
+    }
+
+    // La liste ouverte passe par-dessus les lignes suivantes
+    if let Some(index) = world.panel.open {
+        for (option, name) in dropdown_options(CONTROLS[index]).iter().enumerate() {
+            let rect = option_rect(index, option);
+            let highlighted = world.panel.highlight == option;
+            canvas.set_draw_color(if highlighted { Color::RGB(70, 70, 110) } else { Color::RGB(45, 45, 60) });
+            canvas.fill_rect(rect)?;
+            draw_text(canvas, name, rect.x() + 4, rect.y() + 3, 2, if highlighted { FOCUS_COLOR } else { Color::WHITE })?;
         }
-        current_x += (6 * scale) as i32; // 5 columns + 1 for spacing
     }
     Ok(())
 }
 
-fn get_char_map(c: char) -> [[u8; 5]; 7] {
-    match c {
-        '.' => [
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 1, 1, 0, 0],
-            [0, 1, 1, 0, 0],
-        ],
-        '0' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 1, 1],
-            [1, 0, 1, 0, 1],
-            [1, 1, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-        ],
-        '1' => [
-            [0, 0, 1, 0, 0],
-            [0, 1, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 1, 1, 1, 0],
-        ],
-        '2' => [
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 1],
-            [0, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 1, 1, 1, 1],
-        ],
-        '3' => [
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 1],
-            [0, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 1],
-            [0, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-        ],
-        '4' => [
-            [1, 0, 0, 1, 0],
-            [1, 0, 0, 1, 0],
-            [1, 0, 0, 1, 0],
-            [1, 1, 1, 1, 1],
-            [0, 0, 0, 1, 0],
-            [0, 0, 0, 1, 0],
-            [0, 0, 0, 1, 0],
-        ],
-        '5' => [
-            [1, 1, 1, 1, 1],
-            [1, 0, 0, 0, 0],
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 1],
-            [0, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        '6' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 0],
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        '7' => [
-            [1, 1, 1, 1, 1],
-            [0, 0, 0, 1, 0],
-            [0, 0, 1, 0, 0],
-            [0, 1, 0, 0, 0],
-            [0, 1, 0, 0, 0],
-            [0, 1, 0, 0, 0],
-            [0, 1, 0, 0, 0],
-        ],
-        '8' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        '9' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 1],
-            [0, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'A' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 1, 1, 1, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 0, 0, 0, 0],
-        ],
-        'B' => [
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'C' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'F' => [
-            [1, 1, 1, 1, 1],
-            [1, 0, 0, 0, 0],
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'G' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 0],
-            [1, 0, 1, 1, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'I' => [
-            [1, 1, 1, 1, 1],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [1, 1, 1, 1, 1],
-            [0, 0, 0, 0, 0],
-        ],
-        'L' => [
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 1, 1, 1, 1],
-            [0, 0, 0, 0, 0],
-        ],
-        'O' => [
-            [0, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'P' => [
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [1, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'R' => [
-            [1, 1, 1, 1, 0],
-            [1, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [1, 0, 1, 0, 0],
-            [1, 0, 0, 1, 0],
-            [1, 0, 0, 0, 1],
-            [0, 0, 0, 0, 0],
-        ],
-        'S' => [
-            [0, 1, 1, 1, 1],
-            [1, 0, 0, 0, 0],
-            [0, 1, 1, 1, 0],
-            [0, 0, 0, 0, 1],
-            [1, 1, 1, 1, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'T' => [
-            [1, 1, 1, 1, 1],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'V' => [
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [1, 0, 0, 0, 1],
-            [0, 1, 0, 1, 0],
-            [0, 0, 1, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        'W' => [
-            [1, 0, 0, 0, 1],
-            [1, 0, 1, 0, 1],
-            [1, 0, 1, 0, 1],
-            [1, 1, 0, 1, 1],
-            [1, 1, 0, 1, 1],
-            [1, 0, 0, 0, 1],
-            [0, 0, 0, 0, 0],
-        ],
-        ':' => [
-            [0, 0, 0, 0, 0],
-            [0, 1, 1, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 1, 1, 0, 0],
-            [0, 0, 0, 0, 0],
-            [0, 0, 0, 0, 0],
-        ],
-        ' ' => [[0; 5]; 7],
-        _ => [[0; 5]; 7], // Blank for unknown chars
+/// Aide (F1) : chaque action avec ses touches, lues dans la table des raccourcis
+fn draw_help<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(10, 10, 20, 230));
+    canvas.fill_rect(sdl2::rect::Rect::new(0, 0, world.config.sim_width, world.config.screen_height))?;
+    canvas.set_blend_mode(BlendMode::None);
+
+    let line_height = 20;
+    let close_keys: Vec<String> = world.keymap.bindings_for(Action::Help).map(|binding| binding.to_string()).collect();
+    draw_text(canvas, &format!("KEYS ({} TO CLOSE)", close_keys.join(", ")), 20, 20, 2, FOCUS_COLOR)?;
+    // Touches alignées après la plus longue description
+    let keys_x = 20 + ACTIONS.iter().map(|action| text_size(action.description(), 2).0).max().unwrap_or(0) as i32 + 24;
+    for (i, action) in ACTIONS.iter().enumerate() {
+        let keys: Vec<String> = world.keymap.bindings_for(*action).map(|binding| binding.to_string()).collect();
+        let y = 50 + i as i32 * line_height;
+        draw_text(canvas, action.description(), 20, y, 2, Color::WHITE)?;
+        draw_text(canvas, &keys.join(", "), keys_x, y, 2, Color::RGB(200, 200, 210))?;
     }
+    Ok(())
 }
 
-/// Dessine la traînée d'une balle avec effet comète (dégradé de largeur et couleur)
-fn draw_trail(canvas: &mut Canvas<Window>, ball: &Ball) -> Result<(), String> {
+/// Console déroulante en haut de la simulation : sortie récente, puis la ligne en cours
+fn draw_console<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    let line_height = 18;
+    let height = CONSOLE_LINES as i32 * line_height + 30;
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(10, 10, 20, 230));
+    canvas.fill_rect(sdl2::rect::Rect::new(0, 0, world.config.sim_width, height as u32))?;
+    canvas.set_blend_mode(BlendMode::None);
+    canvas.set_draw_color(Color::RGB(100, 100, 120));
+    canvas.draw_line((0, height), (world.config.sim_width as i32, height))?;
+
+    let output = &world.console.output;
+    for (i, line) in output.iter().skip(output.len().saturating_sub(CONSOLE_LINES)).enumerate() {
+        draw_text(canvas, line, 10, 10 + i as i32 * line_height, 2, Color::RGB(200, 200, 210))?;
+    }
+    let prompt = format!("> {}_", world.console.input);
+    draw_text(canvas, &prompt, 10, height - line_height - 4, 2, FOCUS_COLOR)
+}
+
+/// Position et taille de la frise de l'historique, en bas du HUD
+fn timeline_rect(world: &World) -> sdl2::rect::Rect {
+    let width = world.config.screen_width as i32 - HUD_X_OFFSET - 40;
+    sdl2::rect::Rect::new(HUD_X_OFFSET + 20, world.config.screen_height as i32 - 40, width.max(1) as u32, 16)
+}
+
+/// Position relative (0 à 1) le long de la frise pour une abscisse donnée
+pub fn timeline_fraction_along(world: &World, x: i32) -> f32 {
+    let rect = timeline_rect(world);
+    ((x - rect.x()) as f32 / rect.width() as f32).clamp(0.0, 1.0)
+}
+
+/// Position relative sur la frise si le point est dessus
+pub fn timeline_fraction_at(world: &World, x: i32, y: i32) -> Option<f32> {
+    timeline_rect(world)
+        .contains_point((x, y))
+        .then(|| timeline_fraction_along(world, x))
+}
+
+/// Dessine la frise de l'historique : durée disponible et position courante
+pub fn draw_timeline<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    let history = &world.history;
+    let rect = timeline_rect(world);
+    let label = if history.rewinding {
+        "REWIND".to_string()
+    } else if history.scrub.is_some() {
+        "SCRUB".to_string()
+    } else {
+        format!("HISTORY: {:.0}S", history.span_seconds())
+    };
+    draw_text(canvas, &label, rect.x(), rect.y() - 20, 2, Color::WHITE)?;
+
+    canvas.set_draw_color(Color::RGB(60, 60, 80));
+    canvas.fill_rect(rect)?;
+    let fill = (rect.width() as f32 * history.span_seconds() / HISTORY_SECONDS) as u32;
+    if fill > 0 {
+        canvas.set_draw_color(Color::RGB(90, 90, 130));
+        canvas.fill_rect(sdl2::rect::Rect::new(rect.x(), rect.y(), fill.min(rect.width()), rect.height()))?;
+    }
+
+    // Curseur : sur la position de la frise pendant le déplacement, au présent sinon
+    let marker_x = rect.x() + (history.scrub.unwrap_or(1.0) * fill.min(rect.width()) as f32) as i32;
+    canvas.set_draw_color(Color::RGB(255, 220, 120));
+    canvas.fill_rect(sdl2::rect::Rect::new(marker_x - 2, rect.y() - 3, 4, rect.height() + 6))?;
+    Ok(())
+}
+
+/// Affiche les erreurs de configuration dans un cadre par-dessus la simulation
+fn draw_config_errors<B: Backend>(canvas: &mut B, world: &World) -> Result<(), String> {
+    let line_height = 20;
+    let height = (world.config_errors.len() as i32 + 1) * line_height + 20;
+
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(90, 10, 10, 220));
+    canvas.fill_rect(sdl2::rect::Rect::new(10, 10, world.config.sim_width - 20, height as u32))?;
+    canvas.set_blend_mode(BlendMode::None);
+
+    draw_text(canvas, "CONFIG ERRORS:", 20, 20, 2, Color::RGB(255, 200, 200))?;
+    for (i, error) in world.config_errors.iter().enumerate() {
+        draw_text(canvas, &error.to_string(), 20, 20 + (i as i32 + 1) * line_height, 2, Color::WHITE)?;
+    }
+    Ok(())
+}
+
+/// Affiche un message temporaire en bas de la zone de simulation
+pub fn draw_toast<B: Backend>(canvas: &mut B, world: &World, message: &str) -> Result<(), String> {
+    let y = world.config.screen_height as i32 - 40;
+
+    canvas.set_blend_mode(BlendMode::Blend);
+    canvas.set_draw_color(Color::RGBA(20, 20, 30, 200));
+    canvas.fill_rect(sdl2::rect::Rect::new(10, y - 10, world.config.sim_width - 20, 34))?;
+    canvas.set_blend_mode(BlendMode::None);
+
+    draw_aligned_text(canvas, message, world.config.sim_width as i32 / 2, y, 2, Color::RGB(255, 220, 120), Align::Center)
+}
+
+fn draw_text<B: Backend>(canvas: &mut B, text: &str, x: i32, y: i32, scale: u32, color: Color) -> Result<(), String> {
+    draw_aligned_text(canvas, text, x, y, scale, color, Align::Left)
+}
+
+/// Texte sur une ou plusieurs lignes, chacune alignée sur `x`
+fn draw_aligned_text<B: Backend>(canvas: &mut B, text: &str, x: i32, y: i32, scale: u32, color: Color, align: Align) -> Result<(), String> {
+    canvas.set_draw_color(color);
+    for (px, py) in text_pixels(text, x, y, scale, align) {
+        canvas.fill_rect(sdl2::rect::Rect::new(px, py, scale, scale))?;
+    }
+    Ok(())
+}
+
+// --- Géométrie ---
+//
+// Formes calculées une fois, en pixels de la cible, puis tracées par le canvas ou écrites en SVG
+
+/// Anneau : arc de `start_angle` à `end_angle`, l'ouverture étant entre les deux
+pub struct RingShape {
+    pub center: Vector2D,
+    pub radius: f32,
+    pub start_angle: f32,
+    pub end_angle: f32,
+    pub thickness: i32,
+}
+
+pub fn ring_shape(view: &View) -> RingShape {
+    let world = view.world;
+    RingShape {
+        center: view.point(world.circle_center),
+        radius: view.length(world.config.circle_radius),
+        start_angle: world.circle_angle + world.config.circle_gap_angle * 0.5,
+        end_angle: world.circle_angle - world.config.circle_gap_angle * 0.5 + TWO_PI,
+        thickness: view.length(world.config.circle_thickness).max(1.0) as i32,
+    }
+}
+
+/// Pas angulaire adaptatif basé sur le rayon pour un rendu fluide
+pub fn arc_step(radius: f32) -> f32 {
+    (1.0 / radius).clamp(0.005, 0.02)
+}
+
+/// Points successifs d'un arc ; le dernier peut dépasser `end_angle` d'un pas
+pub fn arc_points(center: Vector2D, radius: f32, start_angle: f32, end_angle: f32, step: f32) -> Vec<Vector2D> {
+    let point = |angle: f32| {
+        let (sin_a, cos_a) = angle.sin_cos();
+        Vector2D { x: center.x + radius * cos_a, y: center.y + radius * sin_a }
+    };
+    let mut angle = start_angle;
+    let mut points = vec![point(angle)];
+    while angle < end_angle {
+        angle += step;
+        points.push(point(angle));
+    }
+    points
+}
+
+/// Segment de traînée : sa largeur passe de `width_start` à `width_end`
+pub struct TrailSegment {
+    pub from: Vector2D,
+    pub to: Vector2D,
+    pub normal: Vector2D, // Perpendiculaire unitaire au segment
+    pub width_start: f32,
+    pub width_end: f32,
+    pub progress: f32, // 0.0 (début) -> 1.0 (fin, près de la balle)
+}
+
+/// Traînée effet comète, et segment large qui la relie à la balle
+pub struct TrailShape {
+    pub segments: Vec<TrailSegment>,
+    pub link: Option<TrailSegment>,
+    pub diameter: f32,
+}
+
+fn trail_segment(from: Vector2D, to: Vector2D, width_start: f32, width_end: f32, progress: f32) -> Option<TrailSegment> {
+    let dx = to.x - from.x;
+    let dy = to.y - from.y;
+    let seg_len = (dx * dx + dy * dy).sqrt();
+    if seg_len < 0.5 {
+        return None; // Segment trop court
+    }
+    let normal = Vector2D { x: -dy / seg_len, y: dx / seg_len };
+    Some(TrailSegment { from, to, normal, width_start, width_end, progress })
+}
+
+pub fn trail_shape(view: &View, ball: &Ball) -> TrailShape {
     let trail_len = ball.trail.len();
+    let diameter = view.length(ball.radius * 2.0);
     if trail_len < 2 {
-        return Ok(());
+        return TrailShape { segments: Vec::new(), link: None, diameter };
     }
-    
-    let base_r = ball.color.r as f32;
-    let base_g = ball.color.g as f32;
-    let base_b = ball.color.b as f32;
-    let ball_diameter = ball.radius * 2.0;
-    
-    // Dessiner chaque segment de la traînée
-    for i in 1..trail_len {
-        let progress = i as f32 / trail_len as f32; // 0.0 (début) -> 1.0 (fin, près de la balle)
-        
-        let p1 = ball.trail[i - 1];
-        let p2 = ball.trail[i];
-        
-        // Calculer la direction du segment
-        let dx = p2.x - p1.x;
-        let dy = p2.y - p1.y;
-        let seg_len = (dx * dx + dy * dy).sqrt();
-        
-        if seg_len < 0.5 {
-            continue; // Segment trop court
-        }
-        
-        // Normale perpendiculaire au segment
-        let nx = -dy / seg_len;
-        let ny = dx / seg_len;
-        
-        // Largeur du segment (de 0 au début à diamètre complet à la fin)
-        let width_start = (i - 1) as f32 / trail_len as f32 * ball_diameter;
-        let width_end = progress * ball_diameter;
-        
+
+    // Largeur de 0 au début à diamètre complet à la fin
+    let segments = (1..trail_len)
+        .filter_map(|i| {
+            let progress = i as f32 / trail_len as f32;
+            let width_start = (i - 1) as f32 / trail_len as f32 * diameter;
+            trail_segment(view.point(ball.trail[i - 1]), view.point(ball.trail[i]), width_start, progress * diameter, progress)
+        })
+        .collect();
+    let link = ball.trail.last().and_then(|&last| trail_segment(view.point(last), view.point(ball.position), diameter, diameter, 1.0));
+    TrailShape { segments, link, diameter }
+}
+
+/// Couleur d'une ligne de traînée. `radial_fade` vaut 1 au centre, 0 sur les bords :
+/// plus lumineux au centre, et de transparent à opaque le long de la traînée
+pub fn trail_color(color: Color, progress: f32, radial_fade: f32) -> Color {
+    let alpha = (progress * radial_fade * 220.0) as u8;
+    let brightness = 0.2 + progress * 0.8 * radial_fade;
+    Color::RGBA(
+        (color.r as f32 * brightness).min(255.0) as u8,
+        (color.g as f32 * brightness).min(255.0) as u8,
+        (color.b as f32 * brightness).min(255.0) as u8,
+        alpha,
+    )
+}
+
+/// Couleur d'une ligne du segment qui relie la traînée à la balle
+pub fn trail_link_color(color: Color, radial_fade: f32) -> Color {
+    Color::RGBA(
+        (color.r as f32 * radial_fade).min(255.0) as u8,
+        (color.g as f32 * radial_fade).min(255.0) as u8,
+        (color.b as f32 * radial_fade).min(255.0) as u8,
+        (radial_fade * 240.0) as u8,
+    )
+}
+
+/// Balle : disque, contour, deux lignes diamétrales qui tournent avec elle et un reflet
+pub struct BallShape {
+    pub center: Vector2D,
+    pub radius: f32,
+    pub spokes: [(Vector2D, Vector2D); 2],
+    pub highlight: Vector2D,
+    pub color: Color,
+    pub outline_color: Color,
+    pub line_color: Color,
+    pub highlight_color: Color,
+}
+
+fn shade(color: Color, delta: i32) -> Color {
+    Color::RGB(
+        (color.r as i32 + delta).clamp(0, 255) as u8,
+        (color.g as i32 + delta).clamp(0, 255) as u8,
+        (color.b as i32 + delta).clamp(0, 255) as u8,
+    )
+}
+
+pub fn ball_shape(view: &View, ball: &Ball) -> BallShape {
+    let center = view.point(ball.position);
+    let radius = view.length(ball.radius);
+
+    // Première ligne selon l'angle de rotation, la deuxième perpendiculaire
+    let inner_radius = radius * 0.7;
+    let (sin_r, cos_r) = ball.rotation.sin_cos();
+    let spokes = [
+        (
+            Vector2D { x: center.x + cos_r * inner_radius, y: center.y + sin_r * inner_radius },
+            Vector2D { x: center.x - cos_r * inner_radius, y: center.y - sin_r * inner_radius },
+        ),
+        (
+            Vector2D { x: center.x - sin_r * inner_radius, y: center.y + cos_r * inner_radius },
+            Vector2D { x: center.x + sin_r * inner_radius, y: center.y - cos_r * inner_radius },
+        ),
+    ];
+
+    // Point lumineux près du bord, légèrement décalé, comme un reflet qui tourne
+    let highlight_dist = radius * 0.6;
+    let highlight_angle = ball.rotation + 0.5;
+    let highlight = Vector2D {
+        x: center.x + highlight_angle.cos() * highlight_dist,
+        y: center.y + highlight_angle.sin() * highlight_dist,
+    };
+
+    BallShape {
+        center,
+        radius,
+        spokes,
+        highlight,
+        color: ball.color,
+        outline_color: shade(ball.color, -40),
+        line_color: shade(ball.color, -60),
+        highlight_color: shade(ball.color, 60),
+    }
+}
+
+/// Émetteur : position et bords du cône de direction, de la même taille quel que soit le zoom
+pub struct EmitterShape {
+    pub center: Vector2D,
+    pub radius: f32,
+    pub cone: [Vector2D; 2],
+}
+
+pub fn emitter_shape(view: &View, emitter: &Emitter) -> EmitterShape {
+    let cone_length = 25.0 * view.scale;
+    let center = view.point(emitter.position);
+    let cone = [emitter.direction - emitter.spread, emitter.direction + emitter.spread].map(|angle| {
+        let (sin_a, cos_a) = angle.sin_cos();
+        Vector2D { x: center.x + cos_a * cone_length, y: center.y + sin_a * cone_length }
+    });
+    EmitterShape { center, radius: 6.0 * view.scale, cone }
+}
+
+// --- Tracé ---
+
+/// Dessine la traînée d'une balle avec effet comète (dégradé de largeur et couleur)
+fn draw_trail<B: Backend>(canvas: &mut B, view: &View, ball: &Ball) -> Result<(), String> {
+    let trail = trail_shape(view, ball);
+
+    for segment in &trail.segments {
         // Dessiner des lignes parallèles pour remplir la largeur avec dégradé
-        let max_offset = (width_end * 0.5) as i32;
-        
+        let max_offset = (segment.width_end * 0.5) as i32;
+        // Calculer la largeur à chaque extrémité du segment
+        let w1_ratio = segment.width_start / trail.diameter.max(1.0);
+
         for offset in -max_offset..=max_offset {
             let offset_f = offset as f32;
-            let offset_ratio = if max_offset > 0 { 
-                1.0 - (offset_f.abs() / max_offset as f32) 
-            } else { 
-                1.0 
-            };
-            
-            // Dégradé radial: plus lumineux au centre, plus sombre sur les bords
-            let radial_fade = offset_ratio * offset_ratio; // Courbe quadratique pour effet plus doux
-            
-            // Dégradé longitudinal: de transparent à opaque
-            let alpha = (progress * radial_fade * 220.0) as u8;
-            let brightness = 0.2 + progress * 0.8 * radial_fade;
-            
-            let r = (base_r * brightness).min(255.0) as u8;
-            let g = (base_g * brightness).min(255.0) as u8;
-            let b = (base_b * brightness).min(255.0) as u8;
-            
-            canvas.set_draw_color(Color::RGBA(r, g, b, alpha));
-            
-            // Calculer la largeur à chaque extrémité du segment
-            let w1_ratio = width_start / ball_diameter.max(1.0);
-            let w2_ratio = width_end / ball_diameter.max(1.0);
-            
+            let offset_ratio = if max_offset > 0 { 1.0 - (offset_f.abs() / max_offset as f32) } else { 1.0 };
+            // Courbe quadratique pour effet plus doux
+            canvas.set_draw_color(trail_color(ball.color, segment.progress, offset_ratio * offset_ratio));
+
             // Limiter l'offset selon la largeur à chaque point
-            let offset1 = (offset_f * w1_ratio).clamp(-width_start * 0.5, width_start * 0.5);
+            let offset1 = (offset_f * w1_ratio).clamp(-segment.width_start * 0.5, segment.width_start * 0.5);
             let offset2 = offset_f;
-            
-            let x1 = p1.x + nx * offset1;
-            let y1 = p1.y + ny * offset1;
-            let x2 = p2.x + nx * offset2;
-            let y2 = p2.y + ny * offset2;
-            
-            canvas.draw_line(
-                (x1 as i32, y1 as i32),
-                (x2 as i32, y2 as i32)
-            )?;
+
+            let x1 = segment.from.x + segment.normal.x * offset1;
+            let y1 = segment.from.y + segment.normal.y * offset1;
+            let x2 = segment.to.x + segment.normal.x * offset2;
+            let y2 = segment.to.y + segment.normal.y * offset2;
+            canvas.draw_line((x1 as i32, y1 as i32), (x2 as i32, y2 as i32))?;
         }
     }
-    
+
     // Connecter la traînée à la position actuelle avec un segment large
-    if let Some(last) = ball.trail.last() {
-        let dx = ball.position.x - last.x;
-        let dy = ball.position.y - last.y;
-        let seg_len = (dx * dx + dy * dy).sqrt();
-        
-        if seg_len > 0.5 {
-            let nx = -dy / seg_len;
-            let ny = dx / seg_len;
-            let max_offset = (ball_diameter * 0.5) as i32;
-            
-            for offset in -max_offset..=max_offset {
-                let offset_f = offset as f32;
-                let offset_ratio = 1.0 - (offset_f.abs() / max_offset.max(1) as f32);
-                let radial_fade = offset_ratio * offset_ratio;
-                
-                let alpha = (radial_fade * 240.0) as u8;
-                let r = (base_r * radial_fade).min(255.0) as u8;
-                let g = (base_g * radial_fade).min(255.0) as u8;
-                let b = (base_b * radial_fade).min(255.0) as u8;
-                
-                canvas.set_draw_color(Color::RGBA(r, g, b, alpha));
-                canvas.draw_line(
-                    (last.x as i32 + (nx * offset_f) as i32, last.y as i32 + (ny * offset_f) as i32),
-                    (ball.position.x as i32 + (nx * offset_f) as i32, ball.position.y as i32 + (ny * offset_f) as i32)
-                )?;
-            }
+    if let Some(link) = &trail.link {
+        let max_offset = (trail.diameter * 0.5) as i32;
+        for offset in -max_offset..=max_offset {
+            let offset_f = offset as f32;
+            let offset_ratio = 1.0 - (offset_f.abs() / max_offset.max(1) as f32);
+            canvas.set_draw_color(trail_link_color(ball.color, offset_ratio * offset_ratio));
+            let (nx, ny) = (link.normal.x * offset_f, link.normal.y * offset_f);
+            canvas.draw_line(
+                (link.from.x as i32 + nx as i32, link.from.y as i32 + ny as i32),
+                (link.to.x as i32 + nx as i32, link.to.y as i32 + ny as i32)
+            )?;
         }
     }
-    
+
     Ok(())
 }
 
 /// Dessine une balle avec un indicateur de rotation
-fn draw_ball_with_rotation(canvas: &mut Canvas<Window>, ball: &Ball) -> Result<(), String> {
-    let cx = ball.position.x as i32;
-    let cy = ball.position.y as i32;
-    let radius = ball.radius as i32;
-    
-    // 1. Dessiner le cercle rempli (corps de la balle)
-    draw_filled_circle(canvas, cx, cy, radius, ball.color)?;
-    
-    // 2. Dessiner le contour fin (1px plus foncé)
-    let outline_color = Color::RGB(
-        (ball.color.r as i32 - 40).max(0) as u8,
-        (ball.color.g as i32 - 40).max(0) as u8,
-        (ball.color.b as i32 - 40).max(0) as u8,
-    );
-    draw_circle_outline(canvas, cx, cy, radius, outline_color)?;
-    
-    // 3. Dessiner deux lignes diamétrales qui tournent avec la balle
-    let inner_radius = (ball.radius * 0.7) as f32;
-    
-    // Première ligne (angle de rotation)
-    let (sin_r, cos_r) = ball.rotation.sin_cos();
-    let x1 = ball.position.x + cos_r * inner_radius;
-    let y1 = ball.position.y + sin_r * inner_radius;
-    let x2 = ball.position.x - cos_r * inner_radius;
-    let y2 = ball.position.y - sin_r * inner_radius;
-    
-    // Deuxième ligne perpendiculaire
-    let x3 = ball.position.x - sin_r * inner_radius;
-    let y3 = ball.position.y + cos_r * inner_radius;
-    let x4 = ball.position.x + sin_r * inner_radius;
-    let y4 = ball.position.y - cos_r * inner_radius;
-    
-    // Couleur des lignes (plus sombre)
-    let line_color = Color::RGB(
-        (ball.color.r as i32 - 60).max(0) as u8,
-        (ball.color.g as i32 - 60).max(0) as u8,
-        (ball.color.b as i32 - 60).max(0) as u8,
-    );
-    canvas.set_draw_color(line_color);
-    canvas.draw_line((x1 as i32, y1 as i32), (x2 as i32, y2 as i32))?;
-    canvas.draw_line((x3 as i32, y3 as i32), (x4 as i32, y4 as i32))?;
-    
-    // 4. Point lumineux sur le bord (comme un reflet qui tourne)
-    let highlight_dist = ball.radius * 0.6;
-    let highlight_angle = ball.rotation + 0.5; // Légèrement décalé
-    let hx = ball.position.x + highlight_angle.cos() * highlight_dist;
-    let hy = ball.position.y + highlight_angle.sin() * highlight_dist;
-    
-    let highlight_color = Color::RGB(
-        (ball.color.r as i32 + 60).min(255) as u8,
-        (ball.color.g as i32 + 60).min(255) as u8,
-        (ball.color.b as i32 + 60).min(255) as u8,
-    );
-    draw_filled_circle(canvas, hx as i32, hy as i32, 2, highlight_color)?;
-    
-    Ok(())
+fn draw_ball_with_rotation<B: Backend>(canvas: &mut B, view: &View, ball: &Ball) -> Result<(), String> {
+    let shape = ball_shape(view, ball);
+    let cx = shape.center.x as i32;
+    let cy = shape.center.y as i32;
+    let radius = shape.radius as i32;
+
+    draw_filled_circle(canvas, cx, cy, radius, shape.color)?;
+    draw_circle_outline(canvas, cx, cy, radius, shape.outline_color)?;
+
+    canvas.set_draw_color(shape.line_color);
+    for (from, to) in shape.spokes {
+        canvas.draw_line((from.x as i32, from.y as i32), (to.x as i32, to.y as i32))?;
+    }
+
+    draw_filled_circle(canvas, shape.highlight.x as i32, shape.highlight.y as i32, HIGHLIGHT_RADIUS as i32, shape.highlight_color)
+}
+
+/// Dessine un émetteur
+fn draw_emitter<B: Backend>(canvas: &mut B, view: &View, emitter: &Emitter) -> Result<(), String> {
+    let shape = emitter_shape(view, emitter);
+    let origin = (shape.center.x as i32, shape.center.y as i32);
+
+    canvas.set_draw_color(EMITTER_COLOR);
+    for end in shape.cone {
+        canvas.draw_line(origin, (end.x as i32, end.y as i32))?;
+    }
+    draw_circle_outline(canvas, origin.0, origin.1, shape.radius as i32, EMITTER_COLOR)
+}
+
+/// Dessine le ressort de saisie : une ligne en zigzag de la balle vers le curseur
+fn draw_spring<B: Backend>(canvas: &mut B, from: Vector2D, to: Vector2D) -> Result<(), String> {
+    const COILS: usize = 12;
+    const AMPLITUDE: f32 = 5.0;
+
+    let axis = to - from;
+    let length = axis.length();
+    if length < 1.0 {
+        return Ok(());
+    }
+    let normal = Vector2D { x: -axis.y / length, y: axis.x / length };
+
+    canvas.set_draw_color(Color::RGB(255, 220, 120));
+    let mut previous = from;
+    for i in 1..=COILS {
+        let t = i as f32 / COILS as f32;
+        // Extrémités droites, zigzag au milieu
+        let side = if i == COILS { 0.0 } else if i % 2 == 0 { -AMPLITUDE } else { AMPLITUDE };
+        let point = from + axis * t + normal * side;
+        canvas.draw_line((previous.x as i32, previous.y as i32), (point.x as i32, point.y as i32))?;
+        previous = point;
+    }
+    draw_circle_outline(canvas, to.x as i32, to.y as i32, 4, Color::RGB(255, 220, 120))
 }
 
 /// Dessine le contour d'un cercle (algorithme de Bresenham)
-fn draw_circle_outline(canvas: &mut Canvas<Window>, cx: i32, cy: i32, radius: i32, color: Color) -> Result<(), String> {
+fn draw_circle_outline<B: Backend>(canvas: &mut B, cx: i32, cy: i32, radius: i32, color: Color) -> Result<(), String> {
     canvas.set_draw_color(color);
     
     let mut x = radius;
@@ -502,14 +630,16 @@ fn draw_circle_outline(canvas: &mut Canvas<Window>, cx: i32, cy: i32, radius: i3
     
     while x >= y {
         // Dessiner les 8 points symétriques
-        canvas.draw_point((cx + x, cy + y))?;
-        canvas.draw_point((cx + y, cy + x))?;
-        canvas.draw_point((cx - y, cy + x))?;
-        canvas.draw_point((cx - x, cy + y))?;
-        canvas.draw_point((cx - x, cy - y))?;
-        canvas.draw_point((cx - y, cy - x))?;
-        canvas.draw_point((cx + y, cy - x))?;
-        canvas.draw_point((cx + x, cy - y))?;
+        canvas.draw_points(&[
+            (cx + x, cy + y),
+            (cx + y, cy + x),
+            (cx - y, cy + x),
+            (cx - x, cy + y),
+            (cx - x, cy - y),
+            (cx - y, cy - x),
+            (cx + y, cy - x),
+            (cx + x, cy - y),
+        ])?;
         
         y += 1;
         err += 1 + 2 * y;
@@ -522,7 +652,7 @@ fn draw_circle_outline(canvas: &mut Canvas<Window>, cx: i32, cy: i32, radius: i3
     Ok(())
 }
 
-fn draw_filled_circle(canvas: &mut Canvas<Window>, center_x: i32, center_y: i32, radius: i32, color: Color) -> Result<(), String> {
+fn draw_filled_circle<B: Backend>(canvas: &mut B, center_x: i32, center_y: i32, radius: i32, color: Color) -> Result<(), String> {
     canvas.set_draw_color(color);
     for y in -radius..=radius {
         let x_span = ((radius.pow(2) - y.pow(2)) as f32).sqrt() as i32;
@@ -531,35 +661,83 @@ fn draw_filled_circle(canvas: &mut Canvas<Window>, center_x: i32, center_y: i32,
     Ok(())
 }
 
-fn draw_arc(canvas: &mut Canvas<Window>, center: Vector2D, radius: f32, start_angle: f32, end_angle: f32, thickness: i32, color: Color) -> Result<(), String> {
+fn draw_arc<B: Backend>(canvas: &mut B, center: Vector2D, radius: f32, start_angle: f32, end_angle: f32, thickness: i32, color: Color) -> Result<(), String> {
     canvas.set_draw_color(color);
-    // Pas adaptatif basé sur le rayon pour un rendu fluide
-    let step = (1.0 / radius).max(0.005).min(0.02);
+    let step = arc_step(radius);
     let half_thickness = thickness as f32 * 0.5;
-    
+
     for i in 0..thickness {
         let r = radius - half_thickness + i as f32;
-        let mut angle = start_angle;
-        
-        // Calculer le premier point
-        let (sin_a, cos_a) = angle.sin_cos();
-        let mut p1_x = center.x + r * cos_a;
-        let mut p1_y = center.y + r * sin_a;
-        
-        while angle < end_angle {
-            angle += step;
-            let (sin_b, cos_b) = angle.sin_cos();
-            let p2_x = center.x + r * cos_b;
-            let p2_y = center.y + r * sin_b;
-            
-            canvas.draw_line(
-                (p1_x as i32, p1_y as i32),
-                (p2_x as i32, p2_y as i32)
-            )?;
-            
-            p1_x = p2_x;
-            p1_y = p2_y;
+        let points: Vec<_> = arc_points(center, r, start_angle, end_angle, step).iter().map(|p| (p.x as i32, p.y as i32)).collect();
+        for pair in points.windows(2) {
+            canvas.draw_line(pair[0], pair[1])?;
         }
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::framebuffer::Framebuffer;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+
+    /// Monde sans balle, pour lire l'anneau et le HUD seuls
+    fn empty_world() -> World {
+        let mut world = initialize_world(Config::default(), SimRng::seed_from_u64(1));
+        world.balls.clear();
+        world
+    }
+
+    fn rendered(world: &World, scale: u32) -> Framebuffer {
+        let mut framebuffer = Framebuffer::new(world.config.screen_width * scale, world.config.screen_height * scale);
+        render(&mut framebuffer, world).unwrap();
+        framebuffer
+    }
+
+    #[test]
+    fn ring_is_drawn_with_its_gap() {
+        let world = empty_world();
+        let framebuffer = rendered(&world, 1);
+        let (center, radius) = (world.circle_center, world.config.circle_radius);
+        // L'ouverture est centrée sur l'angle de l'anneau (0 : à droite)
+        assert_eq!(framebuffer.pixel((center.x - radius) as i32, center.y as i32), RING_COLOR);
+        assert_eq!(framebuffer.pixel((center.x + radius) as i32, center.y as i32), BACKGROUND_COLOR);
+        assert_eq!(framebuffer.pixel(center.x as i32, center.y as i32), BACKGROUND_COLOR);
+    }
+
+    #[test]
+    fn ball_is_filled_with_its_color() {
+        let world = initialize_world(Config::default(), SimRng::seed_from_u64(1));
+        let framebuffer = rendered(&world, 1);
+        let ball = &world.balls[0];
+        // Entre les lignes de rotation et le reflet
+        let (sin, cos) = (ball.rotation + std::f32::consts::FRAC_PI_4 + std::f32::consts::PI).sin_cos();
+        let x = ball.position.x + cos * ball.radius * 0.4;
+        let y = ball.position.y + sin * ball.radius * 0.4;
+        assert_eq!(framebuffer.pixel(x as i32, y as i32), ball.color);
+    }
+
+    #[test]
+    fn hud_separates_the_simulation() {
+        let world = empty_world();
+        let framebuffer = rendered(&world, 1);
+        assert_eq!(framebuffer.pixel(HUD_X_OFFSET, 5), SEPARATOR_COLOR);
+        assert_eq!(framebuffer.pixel(HUD_X_OFFSET + 5, 5), HUD_COLOR);
+        assert_eq!(framebuffer.pixel(HUD_X_OFFSET - 5, 5), BACKGROUND_COLOR);
+    }
+
+    #[test]
+    fn hud_text_follows_the_scale() {
+        let world = empty_world();
+        let (x, y) = hud_stat_position(0);
+        // Premier pixel allumé de `F` dans "FPS"
+        let single = rendered(&world, 1);
+        assert_eq!(single.pixel(x, y), Color::WHITE);
+        let double = rendered(&world, 2);
+        assert_eq!(double.pixel(x * 2, y * 2), Color::WHITE);
+        assert_eq!(double.pixel(x * 2 + 3, y * 2 + 3), Color::WHITE);
+        assert_eq!(double.pixel(HUD_X_OFFSET * 2 + 5, 5), HUD_COLOR);
+    }
+}
@@ -0,0 +1,88 @@
+use std::path::Path;
+use sdl2::render::{Canvas, RenderTarget};
+use crate::capture::{ImageFormat, capture, timestamp};
+use crate::game::{World, show_toast};
+use crate::snapshot::Snapshot;
+
+// --- Captures d'écran ---
+
+/// Demande une capture (F12) ; elle est prise après le prochain rendu, avant l'affichage
+pub fn request_screenshot(world: &mut World) {
+    world.screenshot_requested = true;
+}
+
+/// Écrit `screenshot-<date>.png` et, à côté, `screenshot-<date>.snap` : un instantané complet,
+/// relisible avec `--load`, suivi des valeurs du HUD au moment de la capture
+pub fn take_screenshot<T: RenderTarget>(world: &mut World, canvas: &Canvas<T>) {
+    if !world.screenshot_requested {
+        return;
+    }
+    world.screenshot_requested = false;
+
+    let stem = free_stem(&format!("screenshot-{}", timestamp()));
+    let image_path = format!("{}.{}", stem, ImageFormat::Png.extension());
+    let sidecar_path = format!("{}.snap", stem);
+    let result = capture(canvas)
+        .and_then(|mut frame| frame.save(&image_path, ImageFormat::Png))
+        .and_then(|()| std::fs::write(&sidecar_path, sidecar(world, &image_path)).map_err(|e| format!("{}: {}", sidecar_path, e)));
+    let message = match result {
+        Ok(()) => format!("SAVED {}", image_path),
+        Err(e) => format!("SCREENSHOT FAILED: {}", e),
+    };
+    show_toast(world, message);
+}
+
+/// Deux captures dans la même seconde reçoivent un suffixe
+fn free_stem(base: &str) -> String {
+    let taken = |stem: &str| Path::new(&format!("{}.png", stem)).exists() || Path::new(&format!("{}.snap", stem)).exists();
+    let mut stem = base.to_string();
+    let mut suffix = 1;
+    while taken(&stem) {
+        suffix += 1;
+        stem = format!("{}-{}", base, suffix);
+    }
+    stem
+}
+
+/// Instantané du monde (graine, pas, configuration...) puis section `[screenshot]`,
+/// ignorée au chargement
+fn sidecar(world: &World, image_path: &str) -> String {
+    let speed = if world.clock.paused { "paused".to_string() } else { world.clock.speed_label().to_lowercase() };
+    let mut text = Snapshot::capture(world).to_string();
+    text.push_str("\n[screenshot]\n");
+    text.push_str(&format!("image = {}\n", image_path));
+    text.push_str(&format!("fps = {}\n", world.fps));
+    text.push_str(&format!("balls = {}\n", world.balls.len()));
+    text.push_str(&format!("wall_collisions = {}\n", world.total_wall_collisions));
+    text.push_str(&format!("ball_collisions = {}\n", world.total_ball_collisions));
+    text.push_str(&format!("pending = {}\n", world.pending_spawns.len()));
+    text.push_str(&format!("speed = {}\n", speed));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Config, initialize_world};
+    use crate::history::step_world;
+    use crate::rng::SimRng;
+    use crate::snapshot::load_from_file;
+
+    #[test]
+    fn sidecar_loads_back_as_a_snapshot() {
+        let mut world = initialize_world(Config::default(), SimRng::seed_from_u64(3));
+        for _ in 0..10 {
+            step_world(&mut world);
+        }
+        let path = std::env::temp_dir().join(format!("suicideballs-{}-screenshot.snap", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let text = sidecar(&world, "screenshot.png");
+        assert!(text.contains("[screenshot]\nimage = screenshot.png\n"));
+        std::fs::write(&path, text).unwrap();
+
+        let snapshot = load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!((snapshot.tick, snapshot.seed, snapshot.rng), (world.tick, world.seed, world.rng));
+        assert_eq!(snapshot.balls.len(), world.balls.len());
+    }
+}
@@ -0,0 +1,432 @@
+use std::fmt;
+use std::str::FromStr;
+use sdl2::pixels::Color;
+use crate::config::set_field;
+use crate::game::{Ball, Config, Emitter, Vector2D, World, circle_center_for, show_toast};
+use crate::rng::SimRng;
+use crate::spawn::SpawnRequest;
+
+// --- Sauvegarde complète du monde ---
+
+const SNAPSHOT_HEADER: &str = "suicideballs-snapshot";
+const SNAPSHOT_VERSION: u32 = 2;
+pub const QUICKSAVE_PATH: &str = "quicksave.snap";
+
+/// État complet d'une simulation, suffisant pour la reprendre à l'identique
+#[derive(Clone)]
+pub struct Snapshot {
+    pub config: Config,
+    pub tick: u64,
+    pub balls: Vec<Ball>,
+    pub emitters: Vec<Emitter>,
+    pub pending_spawns: Vec<SpawnRequest>,
+    pub policy_state: Vec<f32>,
+    pub circle_angle: f32,
+    pub next_ball_id: u64,
+    pub ball_cap: usize,
+    pub rng: SimRng,
+    pub seed: u64,
+    pub total_wall_collisions: u64,
+    pub total_ball_collisions: u64,
+}
+
+impl Snapshot {
+    pub fn capture(world: &World) -> Self {
+        Self::capture_with_balls(world, world.balls.clone())
+    }
+
+    /// Instantané sans les traînées, pour l'historique en mémoire : elles ne sont pas copiées
+    pub fn capture_compact(world: &World) -> Self {
+        let balls = world
+            .balls
+            .iter()
+            .map(|ball| Ball {
+                id: ball.id,
+                position: ball.position,
+                old_position: ball.old_position,
+                trail: Vec::new(),
+                velocity: ball.velocity,
+                acceleration: ball.acceleration,
+                radius: ball.radius,
+                rotation: ball.rotation,
+                angular_velocity: ball.angular_velocity,
+                color: ball.color,
+            })
+            .collect();
+        Self::capture_with_balls(world, balls)
+    }
+
+    fn capture_with_balls(world: &World, balls: Vec<Ball>) -> Self {
+        Self {
+            config: world.config.clone(),
+            tick: world.tick,
+            balls,
+            emitters: world.emitters.clone(),
+            pending_spawns: world.pending_spawns.iter().copied().collect(),
+            policy_state: world.spawn_policy.save_state(),
+            circle_angle: world.circle_angle,
+            next_ball_id: world.next_ball_id,
+            ball_cap: world.ball_cap,
+            rng: world.rng,
+            seed: world.seed,
+            total_wall_collisions: world.total_wall_collisions,
+            total_ball_collisions: world.total_ball_collisions,
+        }
+    }
+
+    /// Remplace l'état de la simulation ; l'état de l'interface (saisie, placement) est remis à zéro.
+    /// La configuration a été vérifiée à la lecture (`parse`) ou à la capture.
+    pub fn restore(&self, world: &mut World) {
+        world.config = self.config.clone();
+        world.config_errors.clear();
+        world.circle_center = circle_center_for(&self.config);
+        world.tick = self.tick;
+        world.balls = self.balls.clone();
+        world.held_ball = None;
+        world.emitters = self.emitters.clone();
+        world.emitter_placement = None;
+        world.pending_spawns = self.pending_spawns.iter().copied().collect();
+        world.spawn_policy = self.config.spawn_policy.build();
+        world.spawn_policy.load_state(&self.policy_state);
+        world.circle_angle = self.circle_angle;
+        world.next_ball_id = self.next_ball_id;
+        world.ball_cap = self.ball_cap;
+        world.rng = self.rng;
+        world.seed = self.seed;
+        world.total_wall_collisions = self.total_wall_collisions;
+        world.total_ball_collisions = self.total_ball_collisions;
+    }
+
+    /// Relit un instantané écrit par `Display`. Les erreurs indiquent le numéro de ligne.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty());
+        let header = format!("{} {}", SNAPSHOT_HEADER, SNAPSHOT_VERSION);
+        match lines.next() {
+            Some((_, line)) if line.trim() == header => {},
+            Some((_, line)) if line.starts_with(SNAPSHOT_HEADER) => {
+                let version = line[SNAPSHOT_HEADER.len()..].trim();
+                return Err(format!("snapshot version {} is not supported, this build reads version {}", version, SNAPSHOT_VERSION));
+            },
+            _ => return Err(format!("not a snapshot: missing `{}` header", header)),
+        }
+
+        let mut snapshot = Snapshot::blank();
+        let mut section = String::new();
+        for (index, raw_line) in lines {
+            let line = raw_line.trim();
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = name.to_string();
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => match section.as_str() {
+                    "config" => set_field(&mut snapshot.config, key.trim(), value.trim()),
+                    "world" => snapshot.set_world_field(key.trim(), value.trim()),
+                    // Valeurs du HUD d'une capture d'écran, pour information seulement
+                    "screenshot" => Ok(()),
+                    _ => Err(format!("unknown section `{}`", section)),
+                },
+                None => Err(format!("expected `key = value`, found `{}`", line)),
+            };
+            result.map_err(|message| format!("line {}: {}", index + 1, message))?;
+        }
+        snapshot.validate()?;
+        Ok(snapshot)
+    }
+
+    /// Mêmes règles qu'un fichier de configuration, pour la configuration comme pour les émetteurs
+    /// en cours : un instantané modifié à la main ne doit pas atteindre la simulation
+    fn validate(&self) -> Result<(), String> {
+        let with_world_emitters = Config { emitters: self.emitters.clone(), ..self.config.clone() };
+        self.config.validate().and_then(|()| with_world_emitters.validate()).map_err(|errors| {
+            let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+            format!("invalid configuration: {}", messages.join(", "))
+        })
+    }
+
+    fn blank() -> Self {
+        Self {
+            config: Config::default(),
+            tick: 0,
+            balls: Vec::new(),
+            emitters: Vec::new(),
+            pending_spawns: Vec::new(),
+            policy_state: Vec::new(),
+            circle_angle: 0.0,
+            next_ball_id: 0,
+            ball_cap: 0,
+            rng: SimRng::seed_from_u64(0),
+            seed: 0,
+            total_wall_collisions: 0,
+            total_ball_collisions: 0,
+        }
+    }
+
+    fn set_world_field(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "tick" => self.tick = parse_value(key, value)?,
+            "circle_angle" => self.circle_angle = parse_value(key, value)?,
+            "next_ball_id" => self.next_ball_id = parse_value(key, value)?,
+            "ball_cap" => self.ball_cap = parse_value(key, value)?,
+            "rng" => self.rng = SimRng::seed_from_u64(parse_value(key, value)?),
+            "seed" => self.seed = parse_value(key, value)?,
+            "total_wall_collisions" => self.total_wall_collisions = parse_value(key, value)?,
+            "total_ball_collisions" => self.total_ball_collisions = parse_value(key, value)?,
+            "policy_state" => self.policy_state = parse_numbers(key, value)?,
+            "emitter" => match parse_numbers(key, value)?.as_slice() {
+                &[x, y, direction, spread, speed_min, speed_max, rate, accumulator] => {
+                    let mut emitter = Emitter::new(Vector2D { x, y }, direction, spread, speed_min, speed_max, rate);
+                    emitter.accumulator = accumulator;
+                    self.emitters.push(emitter);
+                },
+                _ => return Err("emitter expects 8 numbers".to_string()),
+            },
+            "pending" => {
+                let words: Vec<&str> = value.split_whitespace().collect();
+                let &[px, py, vx, vy] = words.as_slice() else {
+                    return Err("pending expects 4 values".to_string());
+                };
+                self.pending_spawns.push(SpawnRequest {
+                    position: parse_optional_vector(key, px, py)?,
+                    velocity: parse_optional_vector(key, vx, vy)?,
+                });
+            },
+            "ball" => {
+                // L'identifiant est entier : un f32 ne le représente plus exactement au-delà de 2^24
+                let (id, numbers) = value.trim().split_once(char::is_whitespace).unwrap_or((value, ""));
+                let id = parse_value(key, id)?;
+                match parse_numbers(key, numbers)?.as_slice() {
+                    &[px, py, ox, oy, vx, vy, radius, rotation, spin, r, g, b] => self.balls.push(Ball {
+                        id,
+                        position: Vector2D { x: px, y: py },
+                        old_position: Vector2D { x: ox, y: oy },
+                        trail: Vec::new(),
+                        velocity: Vector2D { x: vx, y: vy },
+                        acceleration: Vector2D::default(),
+                        radius,
+                        rotation,
+                        angular_velocity: spin,
+                        color: Color::RGB(r as u8, g as u8, b as u8),
+                    }),
+                    _ => return Err("ball expects 13 numbers".to_string()),
+                }
+            },
+            "trail" => {
+                let Some(ball) = self.balls.last_mut() else {
+                    return Err("trail must follow a ball".to_string());
+                };
+                let numbers = parse_numbers(key, value)?;
+                if numbers.len() % 2 != 0 {
+                    return Err("trail expects pairs of coordinates".to_string());
+                }
+                ball.trail = numbers.chunks(2).map(|p| Vector2D { x: p[0], y: p[1] }).collect();
+            },
+            _ => return Err(format!("unknown key `{}`", key)),
+        }
+        Ok(())
+    }
+}
+
+/// Format texte versionné : un en-tête, la section `[config]` au format du fichier
+/// de configuration, puis la section `[world]`
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} {}", SNAPSHOT_HEADER, SNAPSHOT_VERSION)?;
+        writeln!(f)?;
+        writeln!(f, "[config]")?;
+        write!(f, "{}", self.config)?;
+        writeln!(f)?;
+        writeln!(f, "[world]")?;
+        writeln!(f, "tick = {}", self.tick)?;
+        writeln!(f, "circle_angle = {}", self.circle_angle)?;
+        writeln!(f, "next_ball_id = {}", self.next_ball_id)?;
+        writeln!(f, "ball_cap = {}", self.ball_cap)?;
+        writeln!(f, "rng = {}", self.rng.state)?;
+        writeln!(f, "seed = {}", self.seed)?;
+        writeln!(f, "total_wall_collisions = {}", self.total_wall_collisions)?;
+        writeln!(f, "total_ball_collisions = {}", self.total_ball_collisions)?;
+        writeln!(f, "policy_state = {}", join_numbers(&self.policy_state))?;
+        for e in &self.emitters {
+            writeln!(
+                f,
+                "emitter = {} {} {} {} {} {} {} {}",
+                e.position.x, e.position.y, e.direction, e.spread, e.speed_min, e.speed_max, e.rate, e.accumulator
+            )?;
+        }
+        for request in &self.pending_spawns {
+            writeln!(f, "pending = {} {}", format_optional_vector(request.position), format_optional_vector(request.velocity))?;
+        }
+        for ball in &self.balls {
+            writeln!(
+                f,
+                "ball = {} {} {} {} {} {} {} {} {} {} {} {} {}",
+                ball.id, ball.position.x, ball.position.y, ball.old_position.x, ball.old_position.y,
+                ball.velocity.x, ball.velocity.y, ball.radius, ball.rotation, ball.angular_velocity,
+                ball.color.r, ball.color.g, ball.color.b
+            )?;
+            let trail: Vec<f32> = ball.trail.iter().flat_map(|p| [p.x, p.y]).collect();
+            writeln!(f, "trail = {}", join_numbers(&trail))?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value `{}` for `{}`", value, key))
+}
+
+fn parse_numbers(key: &str, value: &str) -> Result<Vec<f32>, String> {
+    value.split_whitespace().map(|word| parse_value(key, word)).collect()
+}
+
+/// Un vecteur absent s'écrit `- -`
+fn parse_optional_vector(key: &str, x: &str, y: &str) -> Result<Option<Vector2D>, String> {
+    if x == "-" && y == "-" {
+        return Ok(None);
+    }
+    Ok(Some(Vector2D { x: parse_value(key, x)?, y: parse_value(key, y)? }))
+}
+
+fn format_optional_vector(vector: Option<Vector2D>) -> String {
+    match vector {
+        Some(v) => format!("{} {}", v.x, v.y),
+        None => "- -".to_string(),
+    }
+}
+
+fn join_numbers(numbers: &[f32]) -> String {
+    numbers.iter().map(|n| n.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+// --- Fichiers ---
+
+pub fn save_to_file(world: &World, path: &str) -> Result<(), String> {
+    std::fs::write(path, Snapshot::capture(world).to_string()).map_err(|e| format!("{}: {}", path, e))
+}
+
+pub fn load_from_file(path: &str) -> Result<Snapshot, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    Snapshot::parse(&text).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Sauvegarde rapide (F5)
+pub fn quick_save(world: &mut World) {
+    let message = match save_to_file(world, QUICKSAVE_PATH) {
+        Ok(()) => format!("SAVED {}", QUICKSAVE_PATH),
+        Err(e) => format!("SAVE FAILED: {}", e),
+    };
+    show_toast(world, message);
+}
+
+/// Charge un instantané dans la simulation en cours. La fenêtre ne pouvant pas changer
+/// de taille en cours de route, un instantané d'une autre taille doit être chargé au démarrage avec `--load`.
+pub fn load_into(world: &mut World, path: &str) -> Result<(), String> {
+    let snapshot = load_from_file(path)?;
+    if !same_window_size(&snapshot.config, &world.config) {
+        return Err(format!("restart with --load {}: window size differs", path));
+    }
+    snapshot.restore(world);
+    Ok(())
+}
+
+/// Chargement rapide (F9)
+pub fn quick_load(world: &mut World) {
+    let message = match load_into(world, QUICKSAVE_PATH) {
+        Ok(()) => format!("LOADED {}", QUICKSAVE_PATH),
+        Err(e) => format!("LOAD FAILED: {}", e),
+    };
+    show_toast(world, message);
+}
+
+fn same_window_size(a: &Config, b: &Config) -> bool {
+    a.screen_width == b.screen_width && a.sim_width == b.sim_width && a.screen_height == b.screen_height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::initialize_world;
+
+    fn world() -> World {
+        initialize_world(Config::default(), SimRng::seed_from_u64(1))
+    }
+
+    #[test]
+    fn invalid_emitter_range_is_refused() {
+        let mut world = world();
+        world.emitters.push(Emitter::new(Vector2D { x: 300.0, y: 300.0 }, 0.0, 0.5, 2.0, 5.0, 1.0));
+        let text = Snapshot::capture(&world).to_string();
+        assert!(Snapshot::parse(&text).is_ok());
+
+        // Plage de vitesse inversée, modifiée à la main
+        let edited = text.replace("emitter = 300 300 0 0.5 2 5 1 0", "emitter = 300 300 0 0.5 5 2 1 0");
+        assert_ne!(edited, text);
+        let error = Snapshot::parse(&edited).err().unwrap_or_default();
+        assert!(error.contains("speed_min 5 > speed_max 2"), "{}", error);
+    }
+
+    #[test]
+    fn invalid_config_is_refused() {
+        let mut world = world();
+        world.config.bounciness = 7.0;
+        let error = Snapshot::parse(&Snapshot::capture(&world).to_string()).err().unwrap_or_default();
+        assert!(error.starts_with("invalid configuration: bounciness"), "{}", error);
+    }
+
+    #[test]
+    fn refused_load_keeps_the_world() {
+        let mut other = world();
+        other.tick = 500;
+        other.config.friction = 2.0;
+        let path = std::env::temp_dir().join(format!("suicideballs-test-{}.snap", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        std::fs::write(&path, Snapshot::capture(&other).to_string()).unwrap();
+
+        let mut world = world();
+        let friction = world.config.friction;
+        assert!(load_into(&mut world, &path).is_err());
+        assert_eq!((world.tick, world.config.friction), (0, friction));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn balls_trails_and_emitters_round_trip() {
+        let mut world = world();
+        world.emitters.push(Emitter::new(Vector2D { x: 120.5, y: 300.0 }, 1.25, 0.3, 2.0, 4.5, 3.0));
+        world.emitters[0].accumulator = 0.75;
+        for id in [7, (1 << 24) + 1, u64::MAX] {
+            world.balls.push(Ball {
+                id,
+                position: Vector2D { x: 400.25, y: 380.0 },
+                old_position: Vector2D { x: 399.0, y: 381.5 },
+                trail: vec![Vector2D { x: 390.0, y: 385.0 }, Vector2D { x: 395.5, y: 383.0 }],
+                velocity: Vector2D { x: 1.25, y: -1.5 },
+                acceleration: Vector2D::default(),
+                radius: 12.0,
+                rotation: 0.5,
+                angular_velocity: -0.125,
+                color: Color::RGB(200, 30, 90),
+            });
+        }
+        world.next_ball_id = u64::MAX;
+
+        let parsed = Snapshot::parse(&Snapshot::capture(&world).to_string()).unwrap();
+        assert!(parsed.emitters == world.emitters);
+        assert_eq!(parsed.next_ball_id, u64::MAX);
+        assert_eq!(parsed.balls.len(), world.balls.len());
+        for (parsed, ball) in parsed.balls.iter().zip(&world.balls) {
+            assert_eq!(parsed.id, ball.id);
+            assert_eq!((parsed.position, parsed.old_position, parsed.velocity), (ball.position, ball.old_position, ball.velocity));
+            assert_eq!((parsed.radius, parsed.rotation, parsed.angular_velocity), (ball.radius, ball.rotation, ball.angular_velocity));
+            assert_eq!(parsed.color, ball.color);
+            assert_eq!(parsed.trail, ball.trail);
+        }
+    }
+
+    #[test]
+    fn older_versions_are_refused() {
+        let text = Snapshot::capture(&world()).to_string().replacen("suicideballs-snapshot 2", "suicideballs-snapshot 1", 1);
+        let error = Snapshot::parse(&text).err().unwrap_or_default();
+        assert_eq!(error, "snapshot version 1 is not supported, this build reads version 2");
+    }
+}
@@ -0,0 +1,290 @@
+use crate::game::{Vector2D, MAX_PENDING_SPAWNS};
+
+// --- Politiques d'apparition ---
+
+/// Balle sortie de l'anneau pendant ce tick
+pub struct EscapedBall {
+    pub position: Vector2D,
+    pub velocity: Vector2D,
+}
+
+/// État de la simulation visible par les politiques d'apparition
+pub struct SpawnContext {
+    pub ball_count: usize,
+    pub pending_count: usize, // Apparitions déjà en attente d'une place
+    pub balls_to_spawn: u32,
+    pub circle_center: Vector2D,
+    pub spawn_radius: f32, // Distance maximale au centre pour une nouvelle balle
+}
+
+/// Demande d'apparition : `None` laisse la position ou la vitesse au hasard
+#[derive(Clone, Copy, Default)]
+pub struct SpawnRequest {
+    pub position: Option<Vector2D>,
+    pub velocity: Option<Vector2D>,
+}
+
+/// Décide combien de balles apparaissent, où et avec quelle vitesse.
+/// Appelée à chaque tick, avec la liste (éventuellement vide) des balles sorties.
+pub trait SpawnPolicy {
+    fn name(&self) -> &'static str;
+    fn spawn(&mut self, escaped: &[EscapedBall], ctx: &SpawnContext) -> Vec<SpawnRequest>;
+
+    /// État interne à sauvegarder avec le monde (vide pour les politiques sans état)
+    fn save_state(&self) -> Vec<f32> {
+        Vec::new()
+    }
+
+    fn load_state(&mut self, _state: &[f32]) {}
+}
+
+/// Sélection de la politique dans la configuration
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SpawnPolicyKind {
+    /// Chaque balle sortie est remplacée par `balls_to_spawn` balles
+    Multiplier,
+    /// Maintient la population à un nombre fixe de balles
+    FixedPopulation { population: usize },
+    /// Chaque sortie génère `rate` balles ; `rate` décroît vers 1 à chaque sortie
+    ExponentialDecay { initial_rate: f32, decay: f32 },
+    /// Les nouvelles balles apparaissent là où la dernière balle est sortie
+    EscapePoint,
+    /// Les nouvelles balles héritent de la vitesse de la balle sortie
+    InheritVelocity,
+}
+
+impl SpawnPolicyKind {
+    /// Politique suivante, avec ses paramètres par défaut
+    pub fn next(self) -> Self {
+        match self {
+            SpawnPolicyKind::Multiplier => SpawnPolicyKind::FixedPopulation { population: 20 },
+            SpawnPolicyKind::FixedPopulation { .. } => SpawnPolicyKind::ExponentialDecay { initial_rate: 4.0, decay: 0.8 },
+            SpawnPolicyKind::ExponentialDecay { .. } => SpawnPolicyKind::EscapePoint,
+            SpawnPolicyKind::EscapePoint => SpawnPolicyKind::InheritVelocity,
+            SpawnPolicyKind::InheritVelocity => SpawnPolicyKind::Multiplier,
+        }
+    }
+
+    pub fn build(self) -> Box<dyn SpawnPolicy> {
+        match self {
+            SpawnPolicyKind::Multiplier => Box::new(MultiplierPolicy),
+            SpawnPolicyKind::FixedPopulation { population } => Box::new(FixedPopulationPolicy { population }),
+            SpawnPolicyKind::ExponentialDecay { initial_rate, decay } => {
+                Box::new(ExponentialDecayPolicy { rate: initial_rate, decay, pending: 0.0 })
+            },
+            SpawnPolicyKind::EscapePoint => Box::new(EscapePointPolicy),
+            SpawnPolicyKind::InheritVelocity => Box::new(InheritVelocityPolicy),
+        }
+    }
+}
+
+pub struct MultiplierPolicy;
+
+impl SpawnPolicy for MultiplierPolicy {
+    fn name(&self) -> &'static str {
+        "MULTIPLIER"
+    }
+
+    fn spawn(&mut self, escaped: &[EscapedBall], ctx: &SpawnContext) -> Vec<SpawnRequest> {
+        vec![SpawnRequest::default(); escaped.len() * ctx.balls_to_spawn as usize]
+    }
+}
+
+pub struct FixedPopulationPolicy {
+    pub population: usize,
+}
+
+impl SpawnPolicy for FixedPopulationPolicy {
+    fn name(&self) -> &'static str {
+        "FIXED"
+    }
+
+    fn spawn(&mut self, _escaped: &[EscapedBall], ctx: &SpawnContext) -> Vec<SpawnRequest> {
+        // Les apparitions en attente comptent déjà dans la population
+        vec![SpawnRequest::default(); self.population.saturating_sub(ctx.ball_count + ctx.pending_count)]
+    }
+}
+
+pub struct ExponentialDecayPolicy {
+    pub rate: f32,
+    pub decay: f32,
+    pending: f32, // Fraction de balle reportée au prochain tick
+}
+
+impl SpawnPolicy for ExponentialDecayPolicy {
+    fn name(&self) -> &'static str {
+        "EXPONENTIAL"
+    }
+
+    fn spawn(&mut self, escaped: &[EscapedBall], _ctx: &SpawnContext) -> Vec<SpawnRequest> {
+        let mut requests = Vec::new();
+        for _ in escaped {
+            self.pending += self.rate;
+            let count = self.pending.floor();
+            self.pending -= count;
+            // Jamais plus que la file d'attente ne peut en garder
+            let count = (count as usize).min(MAX_PENDING_SPAWNS.saturating_sub(requests.len()));
+            requests.extend(std::iter::repeat_n(SpawnRequest::default(), count));
+            // Le taux converge vers un remplacement 1 pour 1
+            self.rate = 1.0 + (self.rate - 1.0) * self.decay;
+        }
+        requests
+    }
+
+    fn save_state(&self) -> Vec<f32> {
+        vec![self.rate, self.pending]
+    }
+
+    fn load_state(&mut self, state: &[f32]) {
+        if let &[rate, pending] = state {
+            self.rate = rate;
+            self.pending = pending;
+        }
+    }
+}
+
+pub struct EscapePointPolicy;
+
+impl SpawnPolicy for EscapePointPolicy {
+    fn name(&self) -> &'static str {
+        "ESCAPE POINT"
+    }
+
+    fn spawn(&mut self, escaped: &[EscapedBall], ctx: &SpawnContext) -> Vec<SpawnRequest> {
+        let Some(last) = escaped.last() else {
+            return Vec::new();
+        };
+        // Ramener le point de sortie à l'intérieur de l'anneau, vitesse dirigée vers le centre
+        let direction = (last.position - ctx.circle_center).normalized();
+        let position = ctx.circle_center + direction * ctx.spawn_radius;
+        let velocity = direction * -last.velocity.length();
+        let request = SpawnRequest { position: Some(position), velocity: Some(velocity) };
+        vec![request; escaped.len() * ctx.balls_to_spawn as usize]
+    }
+}
+
+pub struct InheritVelocityPolicy;
+
+impl SpawnPolicy for InheritVelocityPolicy {
+    fn name(&self) -> &'static str {
+        "INHERIT"
+    }
+
+    fn spawn(&mut self, escaped: &[EscapedBall], ctx: &SpawnContext) -> Vec<SpawnRequest> {
+        escaped
+            .iter()
+            .flat_map(|parent| {
+                let request = SpawnRequest { position: None, velocity: Some(parent.velocity) };
+                std::iter::repeat_n(request, ctx.balls_to_spawn as usize)
+            })
+            .collect()
+    }
+}
+
+// --- Dépassement de max_balls ---
+
+/// Comportement quand les apparitions demandées dépassent le plafond de balles
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverflowPolicy {
+    /// Les apparitions en trop sont perdues
+    Drop,
+    /// Les apparitions en trop attendent qu'une place se libère
+    Queue,
+    /// Les balles les plus anciennes sont retirées pour faire de la place
+    EvictOldest,
+    /// Les deux plus petites balles fusionnent pour libérer une place
+    MergeSmallest,
+    /// Le plafond monte tant que le rendu tient la cadence, jusqu'à `ceiling`
+    DynamicCap { ceiling: usize },
+}
+
+impl OverflowPolicy {
+    /// Politique suivante, avec ses paramètres par défaut
+    pub fn next(self) -> Self {
+        match self {
+            OverflowPolicy::Drop => OverflowPolicy::Queue,
+            OverflowPolicy::Queue => OverflowPolicy::EvictOldest,
+            OverflowPolicy::EvictOldest => OverflowPolicy::MergeSmallest,
+            OverflowPolicy::MergeSmallest => OverflowPolicy::DynamicCap { ceiling: 500 },
+            OverflowPolicy::DynamicCap { .. } => OverflowPolicy::Drop,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            OverflowPolicy::Drop => "DROP",
+            OverflowPolicy::Queue => "QUEUE",
+            OverflowPolicy::EvictOldest => "EVICT OLDEST",
+            OverflowPolicy::MergeSmallest => "MERGE",
+            OverflowPolicy::DynamicCap { .. } => "DYNAMIC CAP",
+        }
+    }
+
+    /// Les apparitions en attente sont-elles conservées d'un tick à l'autre ?
+    pub fn keeps_pending(self) -> bool {
+        matches!(self, OverflowPolicy::Queue | OverflowPolicy::DynamicCap { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(ball_count: usize, pending_count: usize, balls_to_spawn: u32) -> SpawnContext {
+        SpawnContext { ball_count, pending_count, balls_to_spawn, circle_center: Vector2D::default(), spawn_radius: 100.0 }
+    }
+
+    fn escaped(count: usize) -> Vec<EscapedBall> {
+        (0..count).map(|_| EscapedBall { position: Vector2D { x: 200.0, y: 0.0 }, velocity: Vector2D { x: 3.0, y: 0.0 } }).collect()
+    }
+
+    #[test]
+    fn multiplier_replaces_each_escaped_ball() {
+        let mut policy = SpawnPolicyKind::Multiplier.build();
+        assert_eq!(policy.spawn(&escaped(3), &context(10, 0, 1)).len(), 3);
+        assert_eq!(policy.spawn(&escaped(3), &context(10, 0, 2)).len(), 6);
+        assert!(policy.spawn(&[], &context(10, 0, 2)).is_empty());
+    }
+
+    #[test]
+    fn fixed_population_tops_up_to_the_target() {
+        let mut policy = SpawnPolicyKind::FixedPopulation { population: 20 }.build();
+        assert_eq!(policy.spawn(&[], &context(12, 0, 2)).len(), 8);
+        assert_eq!(policy.spawn(&escaped(5), &context(12, 3, 2)).len(), 5);
+        assert!(policy.spawn(&[], &context(25, 0, 2)).is_empty());
+    }
+
+    #[test]
+    fn exponential_decay_converges_to_one_for_one() {
+        let mut policy = SpawnPolicyKind::ExponentialDecay { initial_rate: 2.2, decay: 0.5 }.build();
+        // Taux 2.2, 1.6, 1.3, 1.15 : la fraction est reportée d'une sortie à l'autre (0.2, 0.8, 0.1, 0.25)
+        let counts: Vec<usize> = (0..4).map(|_| policy.spawn(&escaped(1), &context(0, 0, 1)).len()).collect();
+        assert_eq!(counts, [2, 1, 2, 1]);
+        for _ in 0..40 {
+            policy.spawn(&escaped(1), &context(0, 0, 1));
+        }
+        let counts: Vec<usize> = (0..10).map(|_| policy.spawn(&escaped(1), &context(0, 0, 1)).len()).collect();
+        assert_eq!(counts, [1; 10]);
+    }
+
+    #[test]
+    fn exponential_decay_never_exceeds_the_queue() {
+        let mut policy = SpawnPolicyKind::ExponentialDecay { initial_rate: 1e30, decay: 0.5 }.build();
+        assert_eq!(policy.spawn(&escaped(3), &context(0, 0, 1)).len(), MAX_PENDING_SPAWNS);
+    }
+
+    #[test]
+    fn exponential_state_survives_save_and_load() {
+        let kind = SpawnPolicyKind::ExponentialDecay { initial_rate: 3.3, decay: 0.8 };
+        let mut policy = kind.build();
+        policy.spawn(&escaped(2), &context(0, 0, 1));
+        let state = policy.save_state();
+
+        let mut restored = kind.build();
+        restored.load_state(&state);
+        assert_eq!(restored.save_state(), state);
+        for _ in 0..5 {
+            assert_eq!(restored.spawn(&escaped(1), &context(0, 0, 1)).len(), policy.spawn(&escaped(1), &context(0, 0, 1)).len());
+        }
+    }
+}
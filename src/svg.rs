@@ -0,0 +1,296 @@
+use std::fmt;
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::BlendMode;
+use crate::backend::Backend;
+use crate::capture::timestamp;
+use crate::font::{Align, text_pixels};
+use crate::game::{World, Vector2D, show_toast};
+use crate::rendering::{
+    View, BACKGROUND_COLOR, EMITTER_COLOR, HIGHLIGHT_RADIUS, HUD_COLOR, HUD_TEXT_SCALE, HUD_X_OFFSET, RING_COLOR, SEPARATOR_COLOR,
+    arc_points, arc_step, ball_shape, draw_panel, draw_timeline, draw_toast, emitter_shape, hud_stat_position, hud_stats,
+    ring_shape, trail_link_color, trail_color, trail_shape, visible_toast,
+};
+
+// --- Export vectoriel ---
+//
+// Mêmes formes que le rendu à l'écran (voir la géométrie de `rendering`), écrites en SVG
+// dans les coordonnées de la fenêtre : l'image peut être agrandie sans perte. Le panneau,
+// la frise et les messages passent par les fonctions de tracé de l'écran, sur `SvgCanvas`.
+
+/// Image SVG de l'état courant : anneau, traînées, balles, émetteurs, message, HUD, panneau et frise
+pub struct Svg<'a>(pub &'a World);
+
+impl fmt::Display for Svg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let world = self.0;
+        let (width, height) = (world.config.screen_width, world.config.screen_height);
+        let view = View { world, scale: 1.0 };
+
+        writeln!(f, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#, w = width, h = height)?;
+        writeln!(f, r#"<rect width="{}" height="{}" fill="{}"/>"#, width, height, rgb(BACKGROUND_COLOR))?;
+
+        let ring = ring_shape(&view);
+        let points = arc_points(ring.center, ring.radius, ring.start_angle, ring.end_angle, arc_step(ring.radius));
+        writeln!(
+            f,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            point_list(&points), rgb(RING_COLOR), ring.thickness
+        )?;
+
+        for ball in &world.balls {
+            // Traînée : chaque segment plus large et plus opaque que le précédent
+            let trail = trail_shape(&view, ball);
+            writeln!(f, r#"<g fill="none" stroke-linecap="round">"#)?;
+            for segment in &trail.segments {
+                let color = trail_color(ball.color, segment.progress, 1.0);
+                write_stroke(f, &[segment.from, segment.to], color, segment.width_end)?;
+            }
+            if let Some(link) = &trail.link {
+                write_stroke(f, &[link.from, link.to], trail_link_color(ball.color, 1.0), trail.diameter)?;
+            }
+            writeln!(f, "</g>")?;
+
+            let shape = ball_shape(&view, ball);
+            writeln!(
+                f,
+                r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}" fill="{}" stroke="{}"/>"#,
+                shape.center.x, shape.center.y, shape.radius, rgb(shape.color), rgb(shape.outline_color)
+            )?;
+            for (from, to) in shape.spokes {
+                writeln!(f, r#"<polyline points="{}" stroke="{}"/>"#, point_list(&[from, to]), rgb(shape.line_color))?;
+            }
+            writeln!(
+                f,
+                r#"<circle cx="{:.2}" cy="{:.2}" r="{}" fill="{}"/>"#,
+                shape.highlight.x, shape.highlight.y, HIGHLIGHT_RADIUS, rgb(shape.highlight_color)
+            )?;
+        }
+
+        for emitter in &world.emitters {
+            let shape = emitter_shape(&view, emitter);
+            writeln!(f, r#"<g fill="none" stroke="{}">"#, rgb(EMITTER_COLOR))?;
+            for end in shape.cone {
+                writeln!(f, r#"<polyline points="{}"/>"#, point_list(&[shape.center, end]))?;
+            }
+            writeln!(f, r#"<circle cx="{:.2}" cy="{:.2}" r="{:.2}"/>"#, shape.center.x, shape.center.y, shape.radius)?;
+            writeln!(f, "</g>")?;
+        }
+
+        if let Some(message) = visible_toast(world) {
+            let mut canvas = SvgCanvas::new(world);
+            draw_toast(&mut canvas, world, message).map_err(|_| fmt::Error)?;
+            writeln!(f, "<g><title>{}</title>\n{}</g>", escape(message), canvas.finish())?;
+        }
+
+        // HUD : fond, séparateur et statistiques dans la police de l'écran
+        writeln!(
+            f,
+            r#"<rect x="{}" y="0" width="{}" height="{}" fill="{}"/>"#,
+            HUD_X_OFFSET, width - world.config.sim_width, height, rgb(HUD_COLOR)
+        )?;
+        writeln!(f, r#"<polyline points="{x},0 {x},{}" stroke="{}"/>"#, height, rgb(SEPARATOR_COLOR), x = HUD_X_OFFSET as f32 + 0.5)?;
+        for (i, text) in hud_stats(world).iter().enumerate() {
+            let (x, y) = hud_stat_position(i);
+            writeln!(f, r#"<path fill="{}" d="{}"><title>{}</title></path>"#, rgb(Color::WHITE), text_path(text, x, y), escape(text))?;
+        }
+
+        // Panneau de réglages et frise de l'historique
+        let mut canvas = SvgCanvas::new(world);
+        draw_panel(&mut canvas, world).map_err(|_| fmt::Error)?;
+        draw_timeline(&mut canvas, world).map_err(|_| fmt::Error)?;
+        writeln!(f, "<g>\n{}</g>", canvas.finish())?;
+
+        writeln!(f, "</svg>")
+    }
+}
+
+pub fn write_svg(world: &World, path: &str) -> Result<(), String> {
+    std::fs::write(path, Svg(world).to_string()).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Export de l'image courante (raccourci) dans `frame-<date>.svg`
+pub fn export_svg(world: &mut World) {
+    let path = format!("frame-{}.svg", timestamp());
+    let message = match write_svg(world, &path) {
+        Ok(()) => format!("SAVED {}", path),
+        Err(e) => format!("SVG FAILED: {}", e),
+    };
+    show_toast(world, message);
+}
+
+/// Trait semi-transparent, l'opacité venant de l'alpha de la couleur
+fn write_stroke(f: &mut fmt::Formatter, points: &[Vector2D], color: Color, width: f32) -> fmt::Result {
+    writeln!(
+        f,
+        r#"<polyline points="{}" stroke="{}" stroke-opacity="{:.3}" stroke-width="{:.2}"/>"#,
+        point_list(points), rgb(color), color.a as f32 / 255.0, width
+    )
+}
+
+/// Texte placé dans le document : les caractères réservés de XML sont remplacés par des entités
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn rgb(color: Color) -> String {
+    format!("rgb({},{},{})", color.r, color.g, color.b)
+}
+
+fn point_list(points: &[Vector2D]) -> String {
+    points.iter().map(|p| format!("{:.2},{:.2}", p.x, p.y)).collect::<Vec<_>>().join(" ")
+}
+
+/// Un carré par pixel allumé de la police
+fn text_path(text: &str, x: i32, y: i32) -> String {
+    let size = HUD_TEXT_SCALE;
+    text_pixels(text, x, y, size, Align::Left).iter().map(|(px, py)| format!("M{} {}h{}v{}h-{}z", px, py, size, size, size)).collect()
+}
+
+// --- Cible de dessin SVG ---
+
+/// `Backend` qui écrit des éléments SVG. Les rectangles pleins successifs d'une même couleur
+/// (les pixels de la police surtout) sont regroupés dans un seul chemin.
+struct SvgCanvas {
+    out: String,
+    size: (u32, u32),
+    color: Color,
+    blend: BlendMode,
+    path: String, // Rectangles pleins en attente, dans la couleur courante
+}
+
+impl SvgCanvas {
+    fn new(world: &World) -> SvgCanvas {
+        SvgCanvas {
+            out: String::new(),
+            size: (world.config.screen_width, world.config.screen_height),
+            color: Color::WHITE,
+            blend: BlendMode::None,
+            path: String::new(),
+        }
+    }
+
+    /// Couleur de remplissage ou de trait ; l'alpha ne compte qu'en mode mélange, comme dans SDL
+    fn paint(&self, attribute: &str) -> String {
+        if self.blend == BlendMode::Blend && self.color.a < 255 {
+            format!(r#"{a}="{}" {a}-opacity="{:.3}""#, rgb(self.color), self.color.a as f32 / 255.0, a = attribute)
+        } else {
+            format!(r#"{}="{}""#, attribute, rgb(self.color))
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.path.is_empty() {
+            let element = format!("<path {} d=\"{}\"/>\n", self.paint("fill"), self.path);
+            self.out.push_str(&element);
+            self.path.clear();
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.flush();
+        self.out
+    }
+}
+
+impl Backend for SvgCanvas {
+    fn set_draw_color(&mut self, color: Color) {
+        if color != self.color {
+            self.flush();
+            self.color = color;
+        }
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        if mode != self.blend {
+            self.flush();
+            self.blend = mode;
+        }
+    }
+
+    // L'image est dans les coordonnées de la fenêtre : pas de facteur à appliquer
+    fn set_scale(&mut self, _x: f32, _y: f32) -> Result<(), String> {
+        Ok(())
+    }
+
+    fn output_size(&self) -> Result<(u32, u32), String> {
+        Ok(self.size)
+    }
+
+    fn clear(&mut self) {
+        self.flush();
+        let element = format!("<rect width=\"{}\" height=\"{}\" fill=\"{}\"/>\n", self.size.0, self.size.1, rgb(self.color));
+        self.out.push_str(&element);
+    }
+
+    fn draw_point(&mut self, point: (i32, i32)) -> Result<(), String> {
+        self.fill_rect(Rect::new(point.0, point.1, 1, 1))
+    }
+
+    // Les tracés passent par le centre des pixels, extrémités comprises
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32)) -> Result<(), String> {
+        self.flush();
+        let element = format!(
+            "<polyline points=\"{}.5,{}.5 {}.5,{}.5\" {} stroke-linecap=\"square\"/>\n",
+            from.0, from.1, to.0, to.1, self.paint("stroke")
+        );
+        self.out.push_str(&element);
+        Ok(())
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        self.flush();
+        let element = format!(
+            "<rect x=\"{}.5\" y=\"{}.5\" width=\"{}\" height=\"{}\" fill=\"none\" {}/>\n",
+            rect.x(), rect.y(), rect.width().saturating_sub(1), rect.height().saturating_sub(1), self.paint("stroke")
+        );
+        self.out.push_str(&element);
+        Ok(())
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        let subpath = format!("M{} {}h{}v{}h-{}z", rect.x(), rect.y(), rect.width(), rect.height(), rect.width());
+        self.path.push_str(&subpath);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{initialize_world, Config};
+    use crate::rng::SimRng;
+
+    #[test]
+    fn export_is_well_formed_xml() {
+        let mut world = initialize_world(Config::default(), SimRng::seed_from_u64(1));
+        assert!(!world.balls.is_empty());
+        show_toast(&mut world, r#"SAVED <a & "b">.svg"#.to_string());
+        let text = Svg(&world).to_string();
+        let document = roxmltree::Document::parse(&text).unwrap();
+
+        let root = document.root_element();
+        assert_eq!(root.tag_name().name(), "svg");
+        assert_eq!(root.attribute("width"), Some(world.config.screen_width.to_string().as_str()));
+        // Chaque balle : un disque et son reflet
+        let circles = root.children().filter(|node| node.has_tag_name("circle")).count();
+        assert_eq!(circles, world.balls.len() * 2);
+        // L'anneau, puis le message, le panneau et la frise regroupés
+        assert!(root.children().any(|node| node.has_tag_name("polyline")));
+        let titles: Vec<&str> = root.descendants().filter(|node| node.has_tag_name("title")).filter_map(|node| node.text()).collect();
+        assert!(titles.contains(&r#"SAVED <a & "b">.svg"#));
+        assert!(titles.contains(&format!("BALLS: {}", world.balls.len()).as_str()));
+        let last_group = root.children().rfind(|node| node.has_tag_name("g")).unwrap();
+        assert!(last_group.children().any(|node| node.has_tag_name("path")));
+    }
+}
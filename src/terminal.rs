@@ -0,0 +1,205 @@
+use sdl2::pixels::Color;
+use sdl2::rect::Rect;
+use sdl2::render::BlendMode;
+use crate::backend::Backend;
+use crate::framebuffer::Framebuffer;
+use crate::game::World;
+use crate::rendering::render;
+
+// --- Rendu dans un terminal ---
+
+const BRAILLE_BASE: u32 = 0x2800;
+// Bit de chaque point d'un caractère braille, par colonne puis par ligne
+const BRAILLE_DOTS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+const DOT_CONTRAST: i32 = 48; // Écart de couleur à partir duquel un point se détache du fond
+
+/// Découpage de l'image en caractères
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Glyphs {
+    /// `▀` : deux pixels par caractère, chacun de sa couleur
+    HalfBlocks,
+    /// Braille : 2 x 4 points par caractère, une couleur pour les points et une pour le fond
+    Braille,
+}
+
+impl Glyphs {
+    /// Pixels couverts par un caractère
+    pub fn cell_size(self) -> (u32, u32) {
+        match self {
+            Glyphs::HalfBlocks => (1, 2),
+            Glyphs::Braille => (2, 4),
+        }
+    }
+}
+
+/// Caractère du terminal, avec sa couleur et celle du fond
+#[derive(Clone, Copy, PartialEq)]
+struct Cell {
+    glyph: char,
+    foreground: Color,
+    background: Color,
+}
+
+/// Dessine dans une image en mémoire, puis la traduit en texte pour un terminal en couleurs 24 bits.
+/// Du texte peut être posé par-dessus, un caractère par case.
+pub struct Terminal {
+    pub framebuffer: Framebuffer,
+    glyphs: Glyphs,
+    text: Vec<Option<Cell>>,
+}
+
+impl Terminal {
+    /// Image de `columns` caractères sur `rows` lignes
+    pub fn new(columns: u32, rows: u32, glyphs: Glyphs) -> Self {
+        let (cell_width, cell_height) = glyphs.cell_size();
+        Self {
+            framebuffer: Framebuffer::new(columns * cell_width, rows * cell_height),
+            glyphs,
+            text: vec![None; columns as usize * rows as usize],
+        }
+    }
+
+    pub fn columns(&self) -> u32 {
+        self.framebuffer.width / self.glyphs.cell_size().0
+    }
+
+    pub fn rows(&self) -> u32 {
+        self.framebuffer.height / self.glyphs.cell_size().1
+    }
+
+    /// Pose du texte à partir d'une case ; ce qui dépasse de la ligne est coupé
+    pub fn write_text(&mut self, column: u32, row: u32, text: &str, foreground: Color, background: Color) {
+        let columns = self.columns();
+        if row >= self.rows() {
+            return;
+        }
+        for (offset, glyph) in text.chars().enumerate() {
+            let column = column + offset as u32;
+            if column >= columns {
+                break;
+            }
+            self.text[(row * columns + column) as usize] = Some(Cell { glyph, foreground, background });
+        }
+    }
+
+    /// Texte de l'image : séquences ANSI, une ligne du terminal par ligne de caractères
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for row in 0..self.rows() {
+            let mut previous: Option<(Color, Color)> = None;
+            for column in 0..self.columns() {
+                let cell = self.cell(column, row);
+                // Les couleurs ne sont répétées que lorsqu'elles changent
+                let colors = (cell.foreground, cell.background);
+                if previous != Some(colors) {
+                    let (fg, bg) = colors;
+                    ansi.push_str(&format!("\x1b[38;2;{};{};{};48;2;{};{};{}m", fg.r, fg.g, fg.b, bg.r, bg.g, bg.b));
+                    previous = Some(colors);
+                }
+                ansi.push(cell.glyph);
+            }
+            ansi.push_str("\x1b[0m");
+            if row + 1 < self.rows() {
+                ansi.push_str("\r\n");
+            }
+        }
+        ansi
+    }
+
+    fn cell(&self, column: u32, row: u32) -> Cell {
+        if let Some(cell) = self.text[(row * self.columns() + column) as usize] {
+            return cell;
+        }
+        let (x, y) = (column as i32, row as i32);
+        match self.glyphs {
+            Glyphs::HalfBlocks => Cell {
+                glyph: '▀',
+                foreground: self.framebuffer.pixel(x, y * 2),
+                background: self.framebuffer.pixel(x, y * 2 + 1),
+            },
+            Glyphs::Braille => self.braille_cell(x * 2, y * 4),
+        }
+    }
+
+    /// La couleur la plus présente devient le fond ; les points qui s'en écartent
+    /// sont allumés, dans leur couleur moyenne
+    fn braille_cell(&self, left: i32, top: i32) -> Cell {
+        let mut dots = [(Color::BLACK, 0); 8];
+        for (dx, column) in BRAILLE_DOTS.iter().enumerate() {
+            for (dy, &bit) in column.iter().enumerate() {
+                dots[dx * 4 + dy] = (self.framebuffer.pixel(left + dx as i32, top + dy as i32), bit);
+            }
+        }
+        let background = dots
+            .iter()
+            .max_by_key(|(color, _)| dots.iter().filter(|(other, _)| other == color).count())
+            .map_or(Color::BLACK, |&(color, _)| color);
+
+        let lit: Vec<(Color, u32)> = dots.into_iter().filter(|&(color, _)| distance(color, background) >= DOT_CONTRAST).collect();
+        if lit.is_empty() {
+            return Cell { glyph: ' ', foreground: background, background };
+        }
+        let bits = lit.iter().fold(0, |bits, &(_, bit)| bits | bit);
+        let average = |channel: fn(Color) -> u8| {
+            (lit.iter().map(|&(color, _)| channel(color) as u32).sum::<u32>() / lit.len() as u32) as u8
+        };
+        Cell {
+            glyph: char::from_u32(BRAILLE_BASE + bits).unwrap_or(' '),
+            foreground: Color::RGB(average(|c| c.r), average(|c| c.g), average(|c| c.b)),
+            background,
+        }
+    }
+}
+
+fn distance(a: Color, b: Color) -> i32 {
+    (a.r as i32 - b.r as i32).abs() + (a.g as i32 - b.g as i32).abs() + (a.b as i32 - b.b as i32).abs()
+}
+
+/// Affiche l'image de départ sur la sortie standard, `columns` caractères de large
+pub fn print_frame(world: &World, columns: u32) -> Result<(), String> {
+    let rows = (columns * world.config.screen_height / world.config.screen_width).div_ceil(2).max(1);
+    let mut terminal = Terminal::new(columns, rows, Glyphs::HalfBlocks);
+    render(&mut terminal, world)?;
+    println!("{}", terminal.to_ansi());
+    Ok(())
+}
+
+impl Backend for Terminal {
+    fn set_draw_color(&mut self, color: Color) {
+        self.framebuffer.set_draw_color(color);
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.framebuffer.set_blend_mode(mode);
+    }
+
+    fn set_scale(&mut self, x: f32, y: f32) -> Result<(), String> {
+        self.framebuffer.set_scale(x, y)
+    }
+
+    fn output_size(&self) -> Result<(u32, u32), String> {
+        self.framebuffer.output_size()
+    }
+
+    /// Efface aussi le texte posé par-dessus
+    fn clear(&mut self) {
+        self.framebuffer.clear();
+        self.text.fill(None);
+    }
+
+    fn draw_point(&mut self, point: (i32, i32)) -> Result<(), String> {
+        self.framebuffer.draw_point(point)
+    }
+
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32)) -> Result<(), String> {
+        self.framebuffer.draw_line(from, to)
+    }
+
+    fn draw_rect(&mut self, rect: Rect) -> Result<(), String> {
+        self.framebuffer.draw_rect(rect)
+    }
+
+    fn fill_rect(&mut self, rect: Rect) -> Result<(), String> {
+        self.framebuffer.fill_rect(rect)
+    }
+}
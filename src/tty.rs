@@ -0,0 +1,661 @@
+use std::collections::VecDeque;
+use std::io::{IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+use sdl2::keyboard::{Keycode, Mod, Scancode};
+use sdl2::pixels::Color;
+use crate::capture::offscreen_canvas;
+use crate::console::{toggle_console, console_type, console_backspace, console_history, console_submit};
+use crate::config::ConfigWatcher;
+use crate::game::{World, TOAST_DURATION, reload_config};
+use crate::history::{advance, measure_fps, FPS_WINDOW, HISTORY_SECONDS};
+use crate::input::perform;
+use crate::keymap::{Action, ACTIONS};
+use crate::panel::{CONTROLS, dropdown_options, label, value_text};
+use crate::recorder::{Recorder, record_frame};
+use crate::rendering::{render, hud_stats, CONSOLE_LINES, FOCUS_COLOR, HUD_COLOR, HUD_X_OFFSET};
+use crate::screenshot::take_screenshot;
+use crate::terminal::{Glyphs, Terminal};
+
+// --- Interface dans un terminal ---
+//
+// Même monde, même boucle de simulation et mêmes raccourcis que la fenêtre : l'image est
+// dessinée en braille, le texte du HUD posé par-dessus en caractères du terminal.
+
+const FRAME_TIME: Duration = Duration::from_millis(33);
+const SIZE_INTERVAL: Duration = Duration::from_secs(1); // Relecture de la taille du terminal
+// Le terminal n'envoie pas le relâchement des touches : le retour en arrière s'arrête
+// quand la répétition automatique cesse
+const REWIND_RELEASE: Duration = Duration::from_millis(600);
+// Délai après lequel un Échap sans suite est la touche Échap et non le début d'une séquence
+const ESCAPE_TIMEOUT: Duration = Duration::from_millis(50);
+const DEFAULT_SIZE: (u32, u32) = (80, 24);
+const TEXT_COLOR: Color = Color::RGB(220, 220, 230);
+const OVERLAY_COLOR: Color = Color::RGB(10, 10, 20);
+const ERROR_COLOR: Color = Color::RGB(255, 200, 200);
+
+/// Touche lue sur l'entrée du terminal, traduite dans les termes de SDL pour la table des raccourcis
+struct KeyPress {
+    keycode: Option<Keycode>,
+    scancode: Option<Scancode>,
+    keymod: Mod,
+    text: Option<char>, // Caractère tapé, pour la console
+}
+
+impl KeyPress {
+    fn code(keycode: Keycode, keymod: Mod) -> Self {
+        Self { keycode: Some(keycode), scancode: None, keymod, text: None }
+    }
+}
+
+enum Input {
+    Key(KeyPress),
+    Interrupt, // Ctrl+C
+}
+
+/// Lance la simulation dans le terminal jusqu'à Échap ou Ctrl+C. Le fichier de `--config`
+/// est surveillé comme dans la fenêtre.
+pub fn run_terminal(world: &mut World, mut config_watcher: Option<ConfigWatcher>) -> Result<(), String> {
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return Err("--terminal needs an interactive terminal".to_string());
+    }
+    let _raw_mode = RawMode::enter()?;
+    let keys = read_stdin();
+    let mut stdout = std::io::stdout().lock();
+
+    let mut frame_times = VecDeque::with_capacity(FPS_WINDOW);
+    let mut accumulator = Duration::ZERO;
+    let mut current_time = Instant::now();
+    let mut size = terminal_size();
+    let mut size_read_at = Instant::now();
+    let (columns, rows) = fit(world, size);
+    let mut terminal = Terminal::new(columns, rows, Glyphs::Braille);
+    let mut resized = true;
+    let mut decoder = KeyDecoder::new();
+    let mut rewind_key_at: Option<Instant> = None;
+
+    loop {
+        let new_time = Instant::now();
+        let frame_time = new_time - current_time;
+        current_time = new_time;
+
+        let mut inputs = Vec::new();
+        loop {
+            match keys.try_recv() {
+                Ok(bytes) => inputs.extend(decoder.feed(&bytes)),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Ok(()),
+            }
+        }
+        inputs.extend(decoder.expire());
+        for input in inputs {
+            let Input::Key(key) = input else {
+                return Ok(());
+            };
+            if world.keymap.is_bound(Action::Rewind, key.keycode, key.scancode) && !world.console.open {
+                rewind_key_at = Some(Instant::now());
+            }
+            if !handle_key(world, &key) {
+                return Ok(());
+            }
+        }
+        if rewind_key_at.is_some_and(|at| at.elapsed() >= REWIND_RELEASE) {
+            world.history.rewinding = false;
+            rewind_key_at = None;
+        }
+
+        if let Some(watcher) = config_watcher.as_mut() {
+            reload_config(world, watcher);
+        }
+
+        advance(world, &mut accumulator, frame_time);
+        world.fps = measure_fps(&mut frame_times, frame_time);
+
+        if size_read_at.elapsed() >= SIZE_INTERVAL {
+            size = terminal_size();
+            size_read_at = Instant::now();
+        }
+        let (columns, rows) = fit(world, size);
+        if (terminal.columns(), terminal.rows()) != (columns, rows) {
+            terminal = Terminal::new(columns, rows, Glyphs::Braille);
+            resized = true;
+        }
+        render(&mut terminal, world)?;
+        draw_overlay(&mut terminal, world);
+        capture_frames(world)?;
+
+        // Un terminal redimensionné garde des restes de l'image précédente
+        let clear = if resized { "\x1b[2J" } else { "" };
+        resized = false;
+        write!(stdout, "{}\x1b[H{}", clear, terminal.to_ansi())
+            .and_then(|()| stdout.flush())
+            .map_err(|e| e.to_string())?;
+
+        std::thread::sleep(FRAME_TIME.saturating_sub(new_time.elapsed()));
+    }
+}
+
+/// Exécute la touche ; retourne `false` pour quitter
+fn handle_key(world: &mut World, key: &KeyPress) -> bool {
+    if world.console.open {
+        console_key(world, key);
+        return true;
+    }
+    match world.keymap.action_for(key.keycode, key.scancode, key.keymod) {
+        Some(action) => perform(world, action),
+        None => true,
+    }
+}
+
+/// Clavier de la console ouverte, comme dans la fenêtre
+fn console_key(world: &mut World, key: &KeyPress) {
+    if key.keycode == Some(Keycode::Escape) || world.keymap.is_bound(Action::Console, key.keycode, key.scancode) {
+        toggle_console(world);
+        return;
+    }
+    match key.keycode {
+        Some(Keycode::Return) => console_submit(world),
+        Some(Keycode::Backspace) => console_backspace(world),
+        Some(Keycode::Up) => console_history(world, -1),
+        Some(Keycode::Down) => console_history(world, 1),
+        _ => {
+            if let Some(text) = key.text {
+                console_type(world, text.encode_utf8(&mut [0; 4]));
+            }
+        },
+    }
+}
+
+/// Captures d'écran et clips : rendus hors écran à la taille de la fenêtre, comme en mode headless
+fn capture_frames(world: &mut World) -> Result<(), String> {
+    if !world.screenshot_requested && world.recorder.is_none() {
+        return Ok(());
+    }
+    let mut canvas = offscreen_canvas(world.config.screen_width, world.config.screen_height)?;
+    render(&mut canvas, world)?;
+    take_screenshot(world, &canvas);
+    record_frame(world, &canvas)
+}
+
+/// Plus grande image qui tient dans le terminal en gardant les proportions de la fenêtre.
+/// Les points braille sont à peu près carrés : 2 x 4 points pour un caractère deux fois plus haut que large.
+fn fit(world: &World, (columns, rows): (u32, u32)) -> (u32, u32) {
+    let (width, height) = (world.config.screen_width, world.config.screen_height);
+    let fitted_rows = (columns * height).div_ceil(2 * width);
+    if fitted_rows <= rows {
+        (columns.max(1), fitted_rows.max(1))
+    } else {
+        ((rows * 2 * width / height).max(1), rows.max(1))
+    }
+}
+
+// --- Texte par-dessus l'image ---
+
+/// HUD, panneau, frise, message, console et aide, en caractères du terminal
+fn draw_overlay(terminal: &mut Terminal, world: &World) {
+    let (columns, rows) = (terminal.columns(), terminal.rows());
+    // La colonne du séparateur reste dessinée
+    let hud_column = (columns * HUD_X_OFFSET as u32 / world.config.screen_width + 1).min(columns);
+    let hud_width = columns - hud_column;
+    let sim_width = hud_column.saturating_sub(1);
+
+    for row in 0..rows {
+        write_line(terminal, hud_column, row, hud_width, "", Color::WHITE, HUD_COLOR);
+    }
+    let mut row = 0;
+    for text in hud_stats(world) {
+        write_line(terminal, hud_column + 1, row, hud_width.saturating_sub(1), &text, Color::WHITE, HUD_COLOR);
+        row += 1;
+    }
+    if let Some(recorder) = &world.recorder {
+        let (text, color) = if recorder.capturing() { ("REC", Color::RGB(230, 60, 60)) } else { ("SAVING", FOCUS_COLOR) };
+        let column = columns.saturating_sub(text.len() as u32 + 1);
+        terminal.write_text(column, 0, text, color, HUD_COLOR);
+    }
+
+    // Panneau : une ligne par contrôle, valeur à droite ; la liste ouverte s'insère sous son contrôle
+    row += 1;
+    for (index, &control) in CONTROLS.iter().enumerate() {
+        let focused = world.panel.focus == index;
+        let color = if focused { FOCUS_COLOR } else { Color::WHITE };
+        let marker = if focused { ">" } else { " " };
+        let text = justify(&format!("{}{}", marker, label(control)), &value_text(world, control), hud_width.saturating_sub(1));
+        write_line(terminal, hud_column, row, hud_width, &text, color, HUD_COLOR);
+        row += 1;
+        if world.panel.open == Some(index) {
+            for (option, name) in dropdown_options(control).iter().enumerate() {
+                let highlighted = world.panel.highlight == option;
+                let color = if highlighted { FOCUS_COLOR } else { Color::WHITE };
+                write_line(terminal, hud_column + 2, row, hud_width.saturating_sub(2), name, color, Color::RGB(45, 45, 60));
+                row += 1;
+            }
+        }
+    }
+
+    // Frise de l'historique sur les deux dernières lignes
+    let history = &world.history;
+    let timeline_label = if history.rewinding {
+        "REWIND".to_string()
+    } else if history.scrub.is_some() {
+        "SCRUB".to_string()
+    } else {
+        format!("HISTORY: {:.0}S", history.span_seconds())
+    };
+    let bar_width = hud_width.saturating_sub(2) as usize;
+    let fill = ((bar_width as f32 * history.span_seconds() / HISTORY_SECONDS) as usize).min(bar_width);
+    let marker = ((history.scrub.unwrap_or(1.0) * fill as f32) as usize).min(bar_width.saturating_sub(1));
+    let bar: String = (0..bar_width).map(|i| if i == marker { '|' } else if i < fill { '#' } else { '.' }).collect();
+    write_line(terminal, hud_column + 1, rows.saturating_sub(2), hud_width.saturating_sub(1), &timeline_label, Color::WHITE, HUD_COLOR);
+    write_line(terminal, hud_column + 1, rows.saturating_sub(1), hud_width.saturating_sub(1), &bar, FOCUS_COLOR, HUD_COLOR);
+
+    // Zone de la simulation
+    if !world.config_errors.is_empty() {
+        write_line(terminal, 0, 0, sim_width, " CONFIG ERRORS:", ERROR_COLOR, Color::RGB(90, 10, 10));
+        for (i, error) in world.config_errors.iter().enumerate() {
+            write_line(terminal, 0, i as u32 + 1, sim_width, &format!(" {}", error), Color::WHITE, Color::RGB(90, 10, 10));
+        }
+    }
+    if let Some(toast) = &world.toast
+        && toast.shown_at.elapsed() < TOAST_DURATION
+        && !world.recorder.as_ref().is_some_and(Recorder::capturing)
+    {
+        write_line(terminal, 0, rows.saturating_sub(2), sim_width, &format!(" {}", toast.message), FOCUS_COLOR, OVERLAY_COLOR);
+    }
+    if world.help_open {
+        draw_help(terminal, world, sim_width);
+    }
+    if world.console.open {
+        draw_console(terminal, world, sim_width);
+    }
+}
+
+fn draw_help(terminal: &mut Terminal, world: &World, width: u32) {
+    let keys_of = |action: Action| world.keymap.bindings_for(action).map(|binding| binding.to_string()).collect::<Vec<_>>().join(", ");
+    for row in 0..terminal.rows() {
+        write_line(terminal, 0, row, width, "", TEXT_COLOR, OVERLAY_COLOR);
+    }
+    write_line(terminal, 0, 0, width, &format!(" KEYS ({} TO CLOSE)", keys_of(Action::Help)), FOCUS_COLOR, OVERLAY_COLOR);
+    let description_width = ACTIONS.iter().map(|action| action.description().len()).max().unwrap_or(0) as u32 + 2;
+    for (i, &action) in ACTIONS.iter().enumerate() {
+        let row = i as u32 + 2;
+        write_line(terminal, 1, row, width.saturating_sub(1), action.description(), Color::WHITE, OVERLAY_COLOR);
+        write_line(terminal, 1 + description_width, row, width.saturating_sub(1 + description_width), &keys_of(action), TEXT_COLOR, OVERLAY_COLOR);
+    }
+}
+
+/// Sortie récente en haut de la simulation, puis la ligne en cours
+fn draw_console(terminal: &mut Terminal, world: &World, width: u32) {
+    let lines = CONSOLE_LINES.min(terminal.rows().saturating_sub(1) as usize);
+    let output = &world.console.output;
+    let shown: Vec<&String> = output.iter().skip(output.len().saturating_sub(lines)).collect();
+    for row in 0..lines {
+        let text = shown.get(row).map_or("", |line| line.as_str());
+        write_line(terminal, 0, row as u32, width, &format!(" {}", text), TEXT_COLOR, OVERLAY_COLOR);
+    }
+    write_line(terminal, 0, lines as u32, width, &format!(" > {}_", world.console.input), FOCUS_COLOR, OVERLAY_COLOR);
+}
+
+/// Ligne de `width` caractères : le texte, coupé ou complété par des espaces
+fn write_line(terminal: &mut Terminal, column: u32, row: u32, width: u32, text: &str, foreground: Color, background: Color) {
+    let padded: String = text.chars().chain(std::iter::repeat(' ')).take(width as usize).collect();
+    terminal.write_text(column, row, &padded, foreground, background);
+}
+
+/// Étiquette à gauche, valeur à droite
+fn justify(left: &str, right: &str, width: u32) -> String {
+    let gap = (width as usize).saturating_sub(left.chars().count() + right.chars().count()).max(1);
+    format!("{}{}{}", left, " ".repeat(gap), right)
+}
+
+// --- Terminal en mode brut ---
+
+/// Entrée sans écho ni tampon de ligne, sur l'écran alternatif ; tout est rétabli à la sortie
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enter() -> Result<Self, String> {
+        let saved = stty(&["-g"])?.trim().to_string();
+        stty(&["raw", "-echo"])?;
+        print!("\x1b[?1049h\x1b[?25l");
+        std::io::stdout().flush().map_err(|e| e.to_string())?;
+        Ok(Self { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        print!("\x1b[0m\x1b[?25h\x1b[?1049l");
+        let _ = std::io::stdout().flush();
+        let _ = stty(&[&self.saved]);
+    }
+}
+
+/// `stty` agit sur le terminal de son entrée standard, celle du jeu
+fn stty(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("stty: {}", e))?;
+    if !output.status.success() {
+        return Err(format!("stty: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Colonnes et lignes du terminal
+fn terminal_size() -> (u32, u32) {
+    let size = stty(&["size"]).ok().and_then(|text| {
+        let mut numbers = text.split_whitespace().map(|n| n.parse::<u32>().ok());
+        let rows = numbers.next()??;
+        let columns = numbers.next()??;
+        (rows > 0 && columns > 0).then_some((columns, rows))
+    });
+    size.unwrap_or(DEFAULT_SIZE)
+}
+
+/// Les lectures bloquantes se font dans un fil à part ; chaque lecture arrive en un morceau,
+/// une séquence d'échappement coupée entre deux lectures est recollée par `KeyDecoder`
+fn read_stdin() -> Receiver<Vec<u8>> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        let mut stdin = std::io::stdin();
+        let mut buffer = [0; 64];
+        while let Ok(count) = stdin.read(&mut buffer) {
+            if count == 0 || sender.send(buffer[..count].to_vec()).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+// --- Décodage des touches ---
+
+/// Octets reçus mais pas encore décodés : une séquence d'échappement ou un caractère UTF-8
+/// peuvent arriver en plusieurs lectures
+struct KeyDecoder {
+    pending: Vec<u8>,
+    received_at: Instant,
+}
+
+impl KeyDecoder {
+    fn new() -> Self {
+        Self { pending: Vec::new(), received_at: Instant::now() }
+    }
+
+    /// Touches complètes ; une séquence commencée attend la lecture suivante
+    fn feed(&mut self, bytes: &[u8]) -> Vec<Input> {
+        self.pending.extend_from_slice(bytes);
+        self.received_at = Instant::now();
+        self.decode_pending(false)
+    }
+
+    /// Sans suite après `ESCAPE_TIMEOUT`, ce qui attend est décodé tel quel : un Échap seul est la touche Échap
+    fn expire(&mut self) -> Vec<Input> {
+        if self.pending.is_empty() || self.received_at.elapsed() < ESCAPE_TIMEOUT {
+            return Vec::new();
+        }
+        self.decode_pending(true)
+    }
+
+    fn decode_pending(&mut self, complete: bool) -> Vec<Input> {
+        let (inputs, used) = decode(&self.pending, complete);
+        self.pending.drain(..used);
+        inputs
+    }
+}
+
+/// Traduit les octets reçus en touches et retourne le nombre d'octets lus. Échap suivi d'une
+/// séquence connue est une touche spéciale, suivi d'un caractère c'est Alt. Une séquence ou un
+/// caractère coupé en fin de lecture n'est pas lu, sauf si `complete` : Échap est alors la touche Échap.
+fn decode(bytes: &[u8], complete: bool) -> (Vec<Input>, usize) {
+    let mut inputs = Vec::new();
+    let mut rest = bytes;
+    while let Some((&byte, tail)) = rest.split_first() {
+        let start = bytes.len() - rest.len();
+        rest = tail;
+        let key = match byte {
+            0x03 => {
+                inputs.push(Input::Interrupt);
+                continue;
+            },
+            0x1b if !complete && incomplete_escape(rest) => return (inputs, start),
+            0x1b => match escape_sequence(rest) {
+                Some((key, length)) => {
+                    rest = &rest[length..];
+                    key
+                },
+                None => KeyPress::code(Keycode::Escape, Mod::NOMOD),
+            },
+            b'\r' | b'\n' => KeyPress::code(Keycode::Return, Mod::NOMOD),
+            b'\t' => KeyPress::code(Keycode::Tab, Mod::NOMOD),
+            0x7f | 0x08 => KeyPress::code(Keycode::Backspace, Mod::NOMOD),
+            // Ctrl+lettre
+            0x01..=0x1a => {
+                let mut key = char_key((b'a' + byte - 1) as char);
+                key.keymod |= Mod::LCTRLMOD;
+                key.text = None;
+                key
+            },
+            _ => {
+                // Caractère UTF-8 complet, pour la console
+                let length = match byte {
+                    0xc0..=0xdf => 2,
+                    0xe0..=0xef => 3,
+                    0xf0..=0xf7 => 4,
+                    _ => 1,
+                };
+                if !complete && length > rest.len() + 1 {
+                    return (inputs, start);
+                }
+                let encoded = &bytes[start..][..length.min(rest.len() + 1)];
+                rest = &rest[encoded.len() - 1..];
+                match std::str::from_utf8(encoded).ok().and_then(|text| text.chars().next()) {
+                    Some(c) => char_key(c),
+                    None => continue,
+                }
+            },
+        };
+        inputs.push(Input::Key(key));
+    }
+    (inputs, bytes.len())
+}
+
+/// Début de séquence dont la fin n'est pas encore arrivée : Échap seul, `O`, ou `[` suivi
+/// seulement de paramètres
+fn incomplete_escape(rest: &[u8]) -> bool {
+    match rest {
+        [] | [b'O'] => true,
+        [b'[', parameters @ ..] => parameters.iter().all(|byte| (0x20..0x40).contains(byte)),
+        _ => false,
+    }
+}
+
+/// Séquence après Échap : `[A` (flèches), `OP` (F1 à F4), `[15;2~` (touches de fonction avec
+/// modificateurs)... Retourne la touche et le nombre d'octets lus.
+fn escape_sequence(bytes: &[u8]) -> Option<(KeyPress, usize)> {
+    match bytes {
+        [b'[', rest @ ..] => {
+            let end = rest.iter().position(|byte| (0x40..=0x7e).contains(byte))?;
+            let parameters: Vec<u32> = std::str::from_utf8(&rest[..end])
+                .ok()?
+                .split(';')
+                .map(|number| number.parse().unwrap_or(1))
+                .collect();
+            let keymod = modifiers(parameters.get(1).copied().unwrap_or(1));
+            let keycode = match rest[end] {
+                b'~' => match parameters.first()? {
+                    11 => Keycode::F1,
+                    12 => Keycode::F2,
+                    13 => Keycode::F3,
+                    14 => Keycode::F4,
+                    15 => Keycode::F5,
+                    17 => Keycode::F6,
+                    18 => Keycode::F7,
+                    19 => Keycode::F8,
+                    20 => Keycode::F9,
+                    21 => Keycode::F10,
+                    23 => Keycode::F11,
+                    24 => Keycode::F12,
+                    1 | 7 => Keycode::Home,
+                    4 | 8 => Keycode::End,
+                    2 => Keycode::Insert,
+                    3 => Keycode::Delete,
+                    5 => Keycode::PageUp,
+                    6 => Keycode::PageDown,
+                    _ => return None,
+                },
+                final_byte => cursor_key(final_byte)?,
+            };
+            Some((KeyPress::code(keycode, keymod), end + 2))
+        },
+        [b'O', final_byte, ..] => Some((KeyPress::code(cursor_key(*final_byte)?, Mod::NOMOD), 2)),
+        [byte, ..] if (0x20..0x7f).contains(byte) => {
+            let mut key = char_key(*byte as char);
+            key.keymod |= Mod::LALTMOD;
+            key.text = None;
+            Some((key, 1))
+        },
+        _ => None,
+    }
+}
+
+/// Dernier octet des flèches et de F1 à F4
+fn cursor_key(byte: u8) -> Option<Keycode> {
+    match byte {
+        b'A' => Some(Keycode::Up),
+        b'B' => Some(Keycode::Down),
+        b'C' => Some(Keycode::Right),
+        b'D' => Some(Keycode::Left),
+        b'H' => Some(Keycode::Home),
+        b'F' => Some(Keycode::End),
+        b'P' => Some(Keycode::F1),
+        b'Q' => Some(Keycode::F2),
+        b'R' => Some(Keycode::F3),
+        b'S' => Some(Keycode::F4),
+        _ => None,
+    }
+}
+
+/// Paramètre de modificateurs xterm : 1 + somme de Maj (1), Alt (2) et Ctrl (4)
+fn modifiers(parameter: u32) -> Mod {
+    let bits = parameter.saturating_sub(1);
+    let mut keymod = Mod::NOMOD;
+    if bits & 1 != 0 {
+        keymod |= Mod::LSHIFTMOD;
+    }
+    if bits & 2 != 0 {
+        keymod |= Mod::LALTMOD;
+    }
+    if bits & 4 != 0 {
+        keymod |= Mod::LCTRLMOD;
+    }
+    keymod
+}
+
+/// Caractère tapé : touche d'un clavier QWERTY US, Maj comprise, et position physique
+fn char_key(c: char) -> KeyPress {
+    const SHIFTED: &str = "~!@#$%^&*()_+{}|:\"<>?";
+    const UNSHIFTED: &str = "`1234567890-=[]\\;',./";
+    let (base, shift) = match SHIFTED.find(c) {
+        Some(index) => (UNSHIFTED[index..].chars().next().unwrap_or(c), true),
+        None if c.is_ascii_uppercase() => (c.to_ascii_lowercase(), true),
+        None => (c, false),
+    };
+    let scancode = match base {
+        'a'..='z' => Scancode::from_i32(Scancode::A as i32 + (base as i32 - 'a' as i32)),
+        '1'..='9' => Scancode::from_i32(Scancode::Num1 as i32 + (base as i32 - '1' as i32)),
+        '0' => Some(Scancode::Num0),
+        ' ' => Some(Scancode::Space),
+        '`' => Some(Scancode::Grave),
+        '-' => Some(Scancode::Minus),
+        '=' => Some(Scancode::Equals),
+        '[' => Some(Scancode::LeftBracket),
+        ']' => Some(Scancode::RightBracket),
+        '\\' => Some(Scancode::Backslash),
+        ';' => Some(Scancode::Semicolon),
+        '\'' => Some(Scancode::Apostrophe),
+        ',' => Some(Scancode::Comma),
+        '.' => Some(Scancode::Period),
+        '/' => Some(Scancode::Slash),
+        _ => None,
+    };
+    KeyPress {
+        keycode: base.is_ascii().then(|| Keycode::from_i32(base as i32)).flatten(),
+        scancode,
+        keymod: if shift { Mod::LSHIFTMOD } else { Mod::NOMOD },
+        text: Some(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Touches décodées : code, modificateurs et texte
+    fn keys(inputs: Vec<Input>) -> Vec<(Option<Keycode>, Mod, Option<char>)> {
+        inputs
+            .into_iter()
+            .map(|input| match input {
+                Input::Key(key) => (key.keycode, key.keymod, key.text),
+                Input::Interrupt => (None, Mod::NOMOD, None),
+            })
+            .collect()
+    }
+
+    fn decode_all(bytes: &[u8]) -> Vec<(Option<Keycode>, Mod, Option<char>)> {
+        let (inputs, used) = decode(bytes, true);
+        assert_eq!(used, bytes.len());
+        keys(inputs)
+    }
+
+    #[test]
+    fn escape_sequences() {
+        assert_eq!(decode_all(b"\x1b[A\x1bOB"), [(Some(Keycode::Up), Mod::NOMOD, None), (Some(Keycode::Down), Mod::NOMOD, None)]);
+        assert_eq!(decode_all(b"\x1bOP"), [(Some(Keycode::F1), Mod::NOMOD, None)]);
+        assert_eq!(decode_all(b"\x1b[15;2~"), [(Some(Keycode::F5), Mod::LSHIFTMOD, None)]);
+        assert_eq!(decode_all(b"\x1b[24;5~"), [(Some(Keycode::F12), Mod::LCTRLMOD, None)]);
+        assert_eq!(decode_all(b"\x1b[1;3C"), [(Some(Keycode::Right), Mod::LALTMOD, None)]);
+        assert_eq!(decode_all(b"\x1bg"), [(Some(Keycode::G), Mod::LALTMOD, None)]);
+        assert_eq!(decode_all(b"\x1b"), [(Some(Keycode::Escape), Mod::NOMOD, None)]);
+        assert!(escape_sequence(b"[99~").is_none());
+    }
+
+    #[test]
+    fn characters_and_control_keys() {
+        assert_eq!(
+            decode_all("G]é\r".as_bytes()),
+            [
+                (Some(Keycode::G), Mod::LSHIFTMOD, Some('G')),
+                (Some(Keycode::RightBracket), Mod::NOMOD, Some(']')),
+                (None, Mod::NOMOD, Some('é')),
+                (Some(Keycode::Return), Mod::NOMOD, None),
+            ]
+        );
+        assert_eq!(decode_all(b"\x12"), [(Some(Keycode::R), Mod::LCTRLMOD, None)]);
+        assert!(matches!(decode(b"\x03", false).0.as_slice(), [Input::Interrupt]));
+    }
+
+    #[test]
+    fn split_sequences_wait_for_the_next_read() {
+        let mut decoder = KeyDecoder::new();
+        assert!(decoder.feed(b"a\x1b").iter().all(|input| matches!(input, Input::Key(key) if key.text == Some('a'))));
+        assert!(decoder.feed(b"[15").is_empty());
+        assert_eq!(keys(decoder.feed(b";2~")), [(Some(Keycode::F5), Mod::LSHIFTMOD, None)]);
+
+        let e_acute = "é".as_bytes();
+        assert!(decoder.feed(&e_acute[..1]).is_empty());
+        assert_eq!(keys(decoder.feed(&e_acute[1..])), [(None, Mod::NOMOD, Some('é'))]);
+
+        // Échap sans suite : rien tant que le délai court, puis la touche Échap
+        assert!(decoder.feed(b"\x1b").is_empty());
+        assert!(decoder.expire().is_empty());
+        std::thread::sleep(ESCAPE_TIMEOUT);
+        assert_eq!(keys(decoder.expire()), [(Some(Keycode::Escape), Mod::NOMOD, None)]);
+        assert!(decoder.pending.is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use crate::capture::{Frame, capture, offscreen_canvas};
+use crate::game::{World, TICKS_PER_SECOND};
+use crate::gif::{GifWriter, frame_delay};
+use crate::history::step_world;
+use crate::rendering::render;
+
+// --- Export vidéo ---
+
+pub const DEFAULT_FPS: u32 = 30;
+pub const DEFAULT_SECONDS: f32 = 10.0;
+pub const MAX_SECONDS: f32 = 3600.0;
+
+/// Format du flux écrit par `--video`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VideoFormat {
+    /// YUV4MPEG2 en 4:2:0, lisible par ffmpeg, mpv, x264...
+    Y4m,
+    /// Images RGB 24 bits brutes, les unes à la suite des autres
+    Rgb,
+    /// GIF animé qui boucle, une palette de 256 couleurs par image
+    Gif,
+}
+
+impl VideoFormat {
+    /// Format déduit de l'extension du fichier, Y4M par défaut
+    pub fn for_path(path: &str) -> Self {
+        if path.to_ascii_lowercase().ends_with(".gif") { VideoFormat::Gif } else { VideoFormat::Y4m }
+    }
+}
+
+impl std::str::FromStr for VideoFormat {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, String> {
+        match text.to_ascii_lowercase().as_str() {
+            "y4m" => Ok(VideoFormat::Y4m),
+            "rgb" => Ok(VideoFormat::Rgb),
+            "gif" => Ok(VideoFormat::Gif),
+            _ => Err(format!("unknown video format `{}` (y4m, rgb or gif)", text)),
+        }
+    }
+}
+
+/// Options de `--video`
+pub struct VideoOptions {
+    pub path: String, // `-` pour la sortie standard
+    pub format: VideoFormat,
+    pub fps: u32,
+    pub seconds: f32,
+    pub scale: u32,
+}
+
+/// Destination des images, selon le format
+enum Encoder {
+    Y4m(BufWriter<Box<dyn Write>>),
+    Rgb(BufWriter<Box<dyn Write>>),
+    Gif(GifWriter<BufWriter<Box<dyn Write>>>),
+}
+
+/// Rend une séquence hors écran à cadence fixe. La simulation avance du nombre de pas
+/// correspondant à chaque image, quel que soit le temps de rendu : le clip est identique
+/// qu'une image prenne 5 ms ou 500 ms.
+pub fn run_video(world: &mut World, options: &VideoOptions) -> Result<(), String> {
+    let width = world.config.screen_width * options.scale;
+    let height = world.config.screen_height * options.scale;
+    let mut canvas = offscreen_canvas(width, height)?;
+    let frames = (options.seconds * options.fps as f32).round() as u64;
+
+    let output: Box<dyn Write> = if options.path == "-" {
+        Box::new(io::stdout().lock())
+    } else {
+        Box::new(File::create(&options.path).map_err(|e| format!("{}: {}", options.path, e))?)
+    };
+    let mut output = BufWriter::new(output);
+    let write_error = |e: io::Error| format!("{}: {}", options.path, e);
+
+    eprintln!("rendering {} frames of {}x{} at {} fps", frames, width, height, options.fps);
+    let mut encoder = match options.format {
+        VideoFormat::Y4m => {
+            writeln!(output, "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C420jpeg", width, height, options.fps).map_err(write_error)?;
+            Encoder::Y4m(output)
+        },
+        VideoFormat::Rgb => Encoder::Rgb(output),
+        VideoFormat::Gif => Encoder::Gif(GifWriter::new(output, width, height).map_err(write_error)?),
+    };
+    // Cadence nominale, affichée par le HUD et lue par le plafond dynamique
+    world.fps = TICKS_PER_SECOND as u32;
+
+    let mut ticks = 0;
+    for index in 0..frames {
+        let target = frame_tick(index, options.fps);
+        while ticks < target {
+            step_world(world);
+            ticks += 1;
+        }
+        render(&mut canvas, world)?;
+        let frame = capture(&canvas)?;
+        match &mut encoder {
+            Encoder::Y4m(output) => write_y4m_frame(output, &frame),
+            Encoder::Rgb(output) => output.write_all(&frame.rgb),
+            Encoder::Gif(gif) => gif.add_frame(&frame, frame_delay(index, options.fps)),
+        }
+        .map_err(write_error)?;
+    }
+    match encoder {
+        Encoder::Y4m(mut output) | Encoder::Rgb(mut output) => output.flush(),
+        Encoder::Gif(gif) => gif.finish().map(drop),
+    }
+    .map_err(write_error)?;
+    if options.path != "-" {
+        eprintln!("wrote {}", options.path);
+    }
+    Ok(())
+}
+
+/// Pas de simulation à atteindre pour l'image `index`, arrondi par défaut
+fn frame_tick(index: u64, fps: u32) -> u64 {
+    index * TICKS_PER_SECOND as u64 / fps as u64
+}
+
+/// Une image YUV 4:2:0 : luminance pleine résolution, chrominance moyennée par blocs de 2x2.
+/// Conversion BT.601 en plage limitée (16-235), celle que supposent les lecteurs.
+fn write_y4m_frame(output: &mut impl Write, frame: &Frame) -> io::Result<()> {
+    let (width, height) = (frame.width as usize, frame.height as usize);
+    let pixel = |x: usize, y: usize| {
+        let offset = (y * width + x) * 3;
+        let rgb = &frame.rgb[offset..offset + 3];
+        (rgb[0] as f32, rgb[1] as f32, rgb[2] as f32)
+    };
+
+    let mut luma = Vec::with_capacity(width * height);
+    for y in 0..height {
+        for x in 0..width {
+            let (r, g, b) = pixel(x, y);
+            luma.push((16.0 + (65.738 * r + 129.057 * g + 25.064 * b) / 256.0).round() as u8);
+        }
+    }
+
+    let (chroma_width, chroma_height) = (width.div_ceil(2), height.div_ceil(2));
+    let mut cb = Vec::with_capacity(chroma_width * chroma_height);
+    let mut cr = Vec::with_capacity(chroma_width * chroma_height);
+    for cy in 0..chroma_height {
+        for cx in 0..chroma_width {
+            let (mut r, mut g, mut b, mut count) = (0.0, 0.0, 0.0, 0.0);
+            for y in (cy * 2)..(cy * 2 + 2).min(height) {
+                for x in (cx * 2)..(cx * 2 + 2).min(width) {
+                    let (pr, pg, pb) = pixel(x, y);
+                    r += pr;
+                    g += pg;
+                    b += pb;
+                    count += 1.0;
+                }
+            }
+            let (r, g, b) = (r / count, g / count, b / count);
+            cb.push((128.0 + (-37.945 * r - 74.494 * g + 112.439 * b) / 256.0).round() as u8);
+            cr.push((128.0 + (112.439 * r - 94.154 * g - 18.285 * b) / 256.0).round() as u8);
+        }
+    }
+
+    output.write_all(b"FRAME\n")?;
+    output.write_all(&luma)?;
+    output.write_all(&cb)?;
+    output.write_all(&cr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Config, initialize_world};
+    use crate::rng::SimRng;
+
+    #[test]
+    fn frames_map_to_whole_ticks() {
+        assert_eq!((0..4).map(|i| frame_tick(i, 30)).collect::<Vec<_>>(), [0, 2, 4, 6]);
+        assert_eq!((0..5).map(|i| frame_tick(i, 24)).collect::<Vec<_>>(), [0, 2, 5, 7, 10]);
+        assert_eq!((0..3).map(|i| frame_tick(i, 60)).collect::<Vec<_>>(), [0, 1, 2]);
+        // Plus d'images que de pas : certaines images montrent le même pas
+        assert_eq!((0..4).map(|i| frame_tick(i, 120)).collect::<Vec<_>>(), [0, 0, 1, 1]);
+        assert_eq!(frame_tick(7 * 60, 7), 3600);
+    }
+
+    #[test]
+    fn y4m_frames_are_planar_420() {
+        let frame = Frame { width: 3, height: 3, rgb: vec![255; 27] };
+        let mut output = Vec::new();
+        write_y4m_frame(&mut output, &frame).unwrap();
+        // Luminance 3x3, puis deux plans de chrominance 2x2 (arrondis au-dessus)
+        assert_eq!(output.len(), 6 + 9 + 4 + 4);
+        assert!(output.starts_with(b"FRAME\n"));
+        assert_eq!(&output[6..15], &[235; 9]);
+        assert_eq!(&output[15..], &[128; 8]);
+    }
+
+    #[test]
+    fn clip_has_a_header_and_one_frame_per_step() {
+        let config = Config { screen_width: 64, sim_width: 64, screen_height: 48, circle_radius: 20.0, ball_radius: 4.0, grid_cell_size: 10.0, ..Config::default() };
+        let mut world = initialize_world(config, SimRng::seed_from_u64(1));
+        let path = std::env::temp_dir().join(format!("suicideballs-{}-clip.y4m", std::process::id()));
+        let path = path.to_string_lossy().to_string();
+        let options = VideoOptions { path: path.clone(), format: VideoFormat::Y4m, fps: 30, seconds: 0.1, scale: 2 };
+        run_video(&mut world, &options).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let header = b"YUV4MPEG2 W128 H96 F30:1 Ip A1:1 C420jpeg\n";
+        assert!(bytes.starts_with(header));
+        let frame_size = 6 + 128 * 96 + 2 * 64 * 48;
+        assert_eq!(bytes.len(), header.len() + 3 * frame_size);
+        // Trois images à 30 ips : pas 0, 2 et 4
+        assert_eq!(world.tick, 4);
+    }
+}